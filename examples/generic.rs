@@ -55,6 +55,17 @@ struct Vec2 {
     y: f64,
 }
 
+impl Vec2 {
+    fn parse(input: &str) -> Result<Self, &'static str> {
+        let (x, y) = input.split_once(',').ok_or("expected comma")?;
+
+        Ok(Self {
+            x: x.parse().map_err(|_| "expected float")?,
+            y: y.parse().map_err(|_| "expected float")?,
+        })
+    }
+}
+
 impl BasicOption for Vec2 {
     fn create_option(
         name: impl Into<String>,
@@ -71,25 +82,19 @@ impl BasicOption for Vec2 {
     fn from_value(
         value: Option<&serenity::all::CommandDataOptionValue>,
     ) -> serenity_commands::Result<Self> {
-        let value = value.ok_or(serenity_commands::Error::MissingRequiredCommandOption)?;
+        let input = String::from_value(value)?;
 
-        let serenity::all::CommandDataOptionValue::String(choice) = value else {
-            return Err(serenity_commands::Error::IncorrectCommandOptionType {
-                got: value.kind(),
-                expected: serenity::all::CommandOptionType::String,
-            });
-        };
+        Self::parse(&input).map_err(serenity_commands::Error::custom)
+    }
 
-        let (x, y) = choice
-            .split_once(',')
-            .ok_or_else(|| serenity_commands::Error::Custom("expected comma".into()))?;
+    fn from_value_named(
+        name: &str,
+        value: Option<&serenity::all::CommandDataOptionValue>,
+    ) -> serenity_commands::Result<Self> {
+        let input = String::from_value_named(name, value)?;
 
-        Ok(Self {
-            x: x.parse()
-                .map_err(|_| serenity_commands::Error::Custom("expected float".into()))?,
-            y: y.parse()
-                .map_err(|_| serenity_commands::Error::Custom("expected float".into()))?,
-        })
+        Self::parse(&input)
+            .map_err(|error| serenity_commands::Error::custom_for(name, input, error))
     }
 }
 
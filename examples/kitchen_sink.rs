@@ -0,0 +1,202 @@
+#![allow(missing_docs, dead_code)]
+
+use std::time::Instant;
+
+use serenity::all::{
+    async_trait, Client, Context, CreateInteractionResponse, CreateInteractionResponseMessage,
+    EventHandler, GatewayIntents, GuildId, Interaction, UserId,
+};
+use serenity_commands::{
+    BasicOption, BoundedString, Command, Commands, Defaulted, SubCommand, SubCommandGroup,
+};
+
+#[derive(Debug, Commands)]
+enum AllCommands {
+    /// Ping the bot.
+    Ping,
+
+    /// Greet a user.
+    Greet {
+        /// The user to greet.
+        user: UserId,
+
+        /// The greeting to use.
+        greeting: Option<String>,
+    },
+
+    /// Award a medal.
+    Award {
+        /// The medal to award.
+        medal: Medal,
+
+        /// The three-letter reason code.
+        code: BoundedString<3, 3>,
+    },
+
+    /// List items.
+    List(ListCommand),
+
+    /// Administrate the bot.
+    Admin(AdminCommand),
+}
+
+impl AllCommands {
+    fn run(self) -> String {
+        match self {
+            Self::Ping => "Pong!".to_string(),
+            Self::Greet { user, greeting } => {
+                format!("{}, <@{user}>!", greeting.as_deref().unwrap_or("Hello"))
+            }
+            Self::Award { medal, code } => format!("Awarded {medal:?} for {}.", code.as_str()),
+            Self::List(list) => list.run(),
+            Self::Admin(admin) => admin.run(),
+        }
+    }
+}
+
+#[derive(Debug, BasicOption)]
+#[choice(option_type = "integer")]
+enum Medal {
+    Gold = 1,
+    Silver = 2,
+    Bronze = 3,
+}
+
+#[derive(Debug, Command)]
+struct ListCommand {
+    /// Whether to include hidden items.
+    show_hidden: Defaulted<bool>,
+
+    #[command(flatten)]
+    pagination: Pagination,
+
+    #[command(skip)]
+    received_at: Option<Instant>,
+}
+
+impl ListCommand {
+    fn run(self) -> String {
+        let Pagination { page, per_page } = self.pagination;
+        let hidden = if *self.show_hidden {
+            " (including hidden items)"
+        } else {
+            ""
+        };
+
+        format!("Listing page {page} with {per_page} items per page{hidden}.")
+    }
+}
+
+#[derive(Debug, SubCommand)]
+struct Pagination {
+    /// The page to show.
+    #[command(default = 1, builder(min_int_value(1)))]
+    page: i64,
+
+    /// The number of items per page.
+    #[command(default = 10, builder(min_int_value(1), max_int_value(25)))]
+    per_page: i64,
+}
+
+#[derive(Debug, Command)]
+enum AdminCommand {
+    /// Shut down the bot.
+    Shutdown,
+
+    /// Manage the configuration.
+    Config(ConfigGroup),
+}
+
+impl AdminCommand {
+    fn run(self) -> String {
+        match self {
+            Self::Shutdown => "Shutting down...".to_string(),
+            Self::Config(ConfigGroup::Get(ConfigKey { key })) => format!("`{key}` is unset."),
+            Self::Config(ConfigGroup::Set { key, value }) => format!("Set `{key}` to `{value}`."),
+        }
+    }
+}
+
+#[derive(Debug, SubCommandGroup)]
+enum ConfigGroup {
+    /// Get a configuration value.
+    Get(ConfigKey),
+
+    /// Set a configuration value.
+    Set {
+        /// The key to set.
+        key: String,
+
+        /// The value to set.
+        value: String,
+    },
+}
+
+#[derive(Debug, SubCommand)]
+struct ConfigKey {
+    /// The key to get.
+    key: String,
+}
+
+// Discord only allows commands to be nested two levels deep, so sub-command
+// groups can only contain sub-commands, not other sub-command groups.
+// Uncommenting the following fails to compile, as `ConfigGroup` does not
+// implement `SubCommand`:
+//
+// #[derive(Debug, SubCommandGroup)]
+// enum TooDeep {
+//     /// Too deeply nested.
+//     Config(ConfigGroup),
+// }
+
+struct Handler {
+    guild_id: GuildId,
+}
+
+#[async_trait]
+impl EventHandler for Handler {
+    async fn ready(&self, ctx: Context, _: serenity::all::Ready) {
+        self.guild_id
+            .set_commands(&ctx, AllCommands::create_commands())
+            .await
+            .unwrap();
+    }
+
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        if let Interaction::Command(command) = interaction {
+            let content = match AllCommands::from_command_data(&command.data) {
+                Ok(command_data) => command_data.run(),
+                Err(error) => format!("Error: {error}"),
+            };
+
+            command
+                .create_response(
+                    ctx,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new().content(content),
+                    ),
+                )
+                .await
+                .unwrap();
+        }
+    }
+}
+
+#[tokio::main]
+pub async fn main() {
+    let token = std::env::var("DISCORD_TOKEN").expect("expected `DISCORD_TOKEN` to be set");
+    let guild_id = std::env::var("DISCORD_GUILD_ID")
+        .expect("expected `DISCORD_GUILD_ID` to be set")
+        .parse()
+        .expect("expected `DISCORD_GUILD_ID` to be a valid guild ID");
+
+    let mut client = Client::builder(token, GatewayIntents::non_privileged())
+        .event_handler(Handler { guild_id })
+        .await
+        .expect("client should be created successfully");
+
+    client
+        .start()
+        .await
+        .expect("client should start successfully");
+}
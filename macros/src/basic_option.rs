@@ -1,10 +1,18 @@
-use darling::{ast::Data, util::SpannedValue, FromDeriveInput, FromMeta, FromVariant};
-use heck::{ToKebabCase, ToTitleCase};
+use darling::{
+    ast::Data,
+    error::Accumulator,
+    util::{Flag, SpannedValue},
+    Error, FromDeriveInput, FromMeta, FromVariant,
+};
+use heck::{
+    ToKebabCase, ToLowerCamelCase, ToPascalCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase,
+    ToTitleCase,
+};
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{Generics, Ident, Lit, LitStr, Type};
+use syn::{Expr, ExprLit, Generics, Ident, Lit, LitFloat, LitStr, Path, Type};
 
-use crate::BuilderMethodList;
+use crate::{BuilderMethodList, CratePaths};
 
 #[derive(Debug, PartialEq, FromMeta)]
 enum OptionType {
@@ -31,6 +39,43 @@ impl OptionType {
     }
 }
 
+/// A case convention for the default values of `"string"` choices, as given
+/// with `#[choice(rename_all = "...")]`.
+#[derive(Debug, Clone, Copy, FromMeta)]
+enum RenameRule {
+    #[darling(rename = "lowercase")]
+    Lower,
+    #[darling(rename = "UPPERCASE")]
+    Upper,
+    #[darling(rename = "PascalCase")]
+    Pascal,
+    #[darling(rename = "camelCase")]
+    Camel,
+    #[darling(rename = "snake_case")]
+    Snake,
+    #[darling(rename = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnake,
+    #[darling(rename = "kebab-case")]
+    Kebab,
+    #[darling(rename = "SCREAMING-KEBAB-CASE")]
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn apply(self, ident: &str) -> String {
+        match self {
+            Self::Lower => ident.to_lowercase(),
+            Self::Upper => ident.to_uppercase(),
+            Self::Pascal => ident.to_pascal_case(),
+            Self::Camel => ident.to_lower_camel_case(),
+            Self::Snake => ident.to_snake_case(),
+            Self::ScreamingSnake => ident.to_shouty_snake_case(),
+            Self::Kebab => ident.to_kebab_case(),
+            Self::ScreamingKebab => ident.to_shouty_kebab_case(),
+        }
+    }
+}
+
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(choice), supports(enum_unit))]
 pub struct Args {
@@ -41,79 +86,220 @@ pub struct Args {
     option_type: SpannedValue<OptionType>,
 
     builder: Option<BuilderMethodList>,
+
+    value_from_name: Flag,
+    rename_all: Option<SpannedValue<RenameRule>>,
+
+    serenity: Option<Path>,
+    serenity_commands: Option<Path>,
 }
 
 impl Args {
-    fn create_option(&self) -> TokenStream {
+    fn value_from_name(&self) -> bool {
+        self.value_from_name.is_present()
+    }
+
+    fn rename_rule(&self) -> RenameRule {
+        self.rename_all
+            .as_deref()
+            .copied()
+            .unwrap_or(RenameRule::Kebab)
+    }
+
+    fn add_choices(&self, paths: &CratePaths) -> TokenStream {
+        let serenity = &paths.serenity;
+        let method_name = self.option_type.method_name(self.option_type.span());
+
         let choices = self
             .data
             .as_ref()
             .take_enum()
             .unwrap()
             .into_iter()
-            .map(Variant::create_option_choice);
+            .map(|variant| variant.add_option_choice(&method_name, self));
 
+        quote! {
+            fn add_choices(
+                option: #serenity::all::CreateCommandOption,
+            ) -> #serenity::all::CreateCommandOption {
+                option
+                    #(#choices)*
+            }
+        }
+    }
+
+    /// Check that `value_from_name` and `rename_all` are only used with string
+    /// choices, and not together.
+    fn validate_attributes(&self, acc: &mut Accumulator) {
+        let is_string = *self.option_type == OptionType::String;
+
+        if self.value_from_name() && !is_string {
+            acc.push(
+                Error::custom("`value_from_name` can only be used with `option_type = \"string\"`")
+                    .with_span(&self.value_from_name.span()),
+            );
+        }
+
+        if let Some(rename_all) = &self.rename_all {
+            if !is_string {
+                acc.push(
+                    Error::custom("`rename_all` can only be used with `option_type = \"string\"`")
+                        .with_span(&rename_all.span()),
+                );
+            }
+
+            if self.value_from_name() {
+                acc.push(
+                    Error::custom("`rename_all` cannot be used with `value_from_name`")
+                        .with_span(&rename_all.span()),
+                );
+            }
+        }
+    }
+
+    /// Check that each choice's value is a literal of the kind `option_type`
+    /// expects, and that choices which cannot fall back to their name have a
+    /// value at all.
+    fn validate_values(&self, acc: &mut Accumulator) {
+        for variant in self.data.as_ref().take_enum().unwrap() {
+            let Some(value) = &variant.value else {
+                match *self.option_type {
+                    OptionType::Integer if !variant.has_literal_discriminant() => acc.push(
+                        Error::custom("`integer` choices need a `value` or a literal discriminant")
+                            .with_span(&variant.ident),
+                    ),
+                    OptionType::Number => acc.push(
+                        Error::custom("`number` choices need a `value`").with_span(&variant.ident),
+                    ),
+                    _ => {}
+                }
+
+                continue;
+            };
+
+            let (expected, matches) = match *self.option_type {
+                OptionType::String => ("a string", matches!(value, Lit::Str(_))),
+                OptionType::Integer => ("an integer", matches!(value, Lit::Int(_))),
+                OptionType::Number => ("a number", matches!(value, Lit::Int(_) | Lit::Float(_))),
+            };
+
+            if !matches {
+                acc.push(
+                    Error::custom(format!(
+                        "expected {expected} literal for this choice's value"
+                    ))
+                    .with_span(value),
+                );
+            }
+        }
+    }
+
+    fn create_option(&self, paths: &CratePaths) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
         let command_option_type = self.option_type.command_option_type();
-        let method_name = self.option_type.method_name(self.option_type.span());
         let builder_methods = &self.builder;
+        let required = !builder_methods
+            .as_ref()
+            .is_some_and(|builder| builder.calls("required"));
+        let required = required.then(|| quote!(.required(true)));
 
         quote! {
             fn create_option(
                 name: impl ::std::convert::Into<::std::string::String>,
                 description: impl ::std::convert::Into<::std::string::String>,
-            ) -> ::serenity::all::CreateCommandOption {
-                ::serenity::all::CreateCommandOption::new(
-                    ::serenity::all::CommandOptionType::#command_option_type,
-                    name,
-                    description,
+            ) -> #serenity::all::CreateCommandOption {
+                <Self as #serenity_commands::ChoiceOption>::add_choices(
+                    #serenity::all::CreateCommandOption::new(
+                        #serenity::all::CommandOptionType::#command_option_type,
+                        name,
+                        description,
+                    )
                 )
-                #(.#method_name(#choices))*
-                .required(true)
+                #required
                 #builder_methods
             }
         }
     }
 
     #[allow(clippy::wrong_self_convention)]
-    fn from_value(&self) -> TokenStream {
+    fn from_value(&self, paths: &CratePaths) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
         let arms = self
             .data
             .as_ref()
             .take_enum()
             .unwrap()
             .into_iter()
-            .map(Variant::from_value);
+            .map(|variant| variant.from_value(self));
 
         let option_type = self.option_type.command_option_type();
 
-        let choice_expr = if *self.option_type == OptionType::String {
-            quote!(choice.as_str())
-        } else {
-            quote!(choice)
+        let match_choice = match *self.option_type {
+            OptionType::String | OptionType::Integer => {
+                let choice_expr = if *self.option_type == OptionType::String {
+                    quote!(choice.as_str())
+                } else {
+                    quote!(choice)
+                };
+
+                quote! {
+                    match #choice_expr {
+                        #(#arms)*
+                        unknown => ::std::result::Result::Err(
+                            #serenity_commands::Error::UnknownChoice(
+                                ::std::string::ToString::to_string(unknown)
+                            )
+                        )
+                    }
+                }
+            }
+            // floats may not survive the round trip through Discord exactly, so
+            // compare them with a relative tolerance of 1e-9 (absolute below 1)
+            // instead of matching on them.
+            OptionType::Number => quote! {
+                let choice = *choice;
+                let matches = |value: f64| {
+                    (choice - value).abs() <= 1e-9 * choice.abs().max(value.abs()).max(1.0)
+                };
+
+                #(#arms)*
+
+                ::std::result::Result::Err(
+                    #serenity_commands::Error::UnknownChoice(
+                        ::std::string::ToString::to_string(&choice)
+                    )
+                )
+            },
         };
 
         quote! {
             fn from_value(
-                value: ::std::option::Option<&::serenity::all::CommandDataOptionValue>
-            ) -> ::serenity_commands::Result<Self> {
+                value: ::std::option::Option<&#serenity::all::CommandDataOptionValue>
+            ) -> #serenity_commands::Result<Self> {
                 let value = value
-                    .ok_or(::serenity_commands::Error::MissingRequiredCommandOption)?;
+                    .ok_or(#serenity_commands::Error::MissingRequiredCommandOption)?;
 
-                let ::serenity::all::CommandDataOptionValue::#option_type(choice) = value else {
-                    return ::std::result::Result::Err(::serenity_commands::Error::IncorrectCommandOptionType {
-                        expected: ::serenity::all::CommandOptionType::#option_type,
-                        got: value.kind(),
-                    });
+                let choice = match value {
+                    #serenity::all::CommandDataOptionValue::#option_type(choice) => choice,
+                    #serenity::all::CommandDataOptionValue::Autocomplete { .. } => {
+                        return ::std::result::Result::Err(
+                            #serenity_commands::Error::UnexpectedAutocompleteOption
+                        );
+                    }
+                    _ => {
+                        return ::std::result::Result::Err(
+                            #serenity_commands::Error::IncorrectCommandOptionType {
+                                name: ::std::option::Option::None,
+                                expected: #serenity::all::CommandOptionType::#option_type,
+                                got: value.kind(),
+                            }
+                        );
+                    }
                 };
 
-                match #choice_expr {
-                    #(#arms)*
-                    unknown => ::std::result::Result::Err(
-                        ::serenity_commands::Error::UnknownChoice(
-                            ::std::string::ToString::to_string(unknown)
-                        )
-                    )
-                }
+                #match_choice
             }
         }
     }
@@ -121,22 +307,38 @@ impl Args {
 
 impl ToTokens for Args {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        let mut acc = Error::accumulator();
+
+        self.validate_attributes(&mut acc);
+        self.validate_values(&mut acc);
+
         let ident = &self.ident;
+        let paths = CratePaths::new(self.serenity.as_ref(), self.serenity_commands.as_ref());
+        let serenity_commands = &paths.serenity_commands;
 
-        let create_option = self.create_option();
-        let from_value = self.from_value();
+        let create_option = self.create_option(&paths);
+        let from_value = self.from_value(&paths);
+        let add_choices = self.add_choices(&paths);
 
         let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
 
-        quote! {
+        let implementation = quote! {
             #[automatically_derived]
-            impl #impl_generics ::serenity_commands::BasicOption for #ident #ty_generics #where_clause {
+            impl #impl_generics #serenity_commands::BasicOption for #ident #ty_generics #where_clause {
                 #create_option
 
                 #from_value
             }
-        }
-        .to_tokens(tokens);
+
+            #[automatically_derived]
+            impl #impl_generics #serenity_commands::ChoiceOption for #ident #ty_generics #where_clause {
+                #add_choices
+            }
+        };
+
+        acc.finish_with(implementation)
+            .unwrap_or_else(Error::write_errors)
+            .to_tokens(tokens);
     }
 }
 
@@ -144,9 +346,12 @@ impl ToTokens for Args {
 #[darling(attributes(choice))]
 pub struct Variant {
     ident: Ident,
+    discriminant: Option<Expr>,
     name: Option<SpannedValue<String>>,
 
     value: Option<Lit>,
+
+    builder: Option<BuilderMethodList>,
 }
 
 impl Variant {
@@ -166,33 +371,80 @@ impl Variant {
         )
     }
 
-    fn value(&self) -> Lit {
-        self.value.clone().unwrap_or_else(|| {
-            let ident_s = self.ident.to_string();
-            Lit::Str(LitStr::new(
-                &ident_s
-                    .strip_prefix("r#")
-                    .unwrap_or(&ident_s)
-                    .to_kebab_case(),
-                self.ident.span(),
-            ))
-        })
+    const fn has_literal_discriminant(&self) -> bool {
+        matches!(
+            self.discriminant,
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Int(_),
+                ..
+            }))
+        )
     }
 
-    fn create_option_choice(&self) -> TokenStream {
+    fn value(&self, args: &Args) -> Lit {
+        let option_type = &*args.option_type;
+
+        let discriminant = match &self.discriminant {
+            Some(Expr::Lit(ExprLit {
+                lit: lit @ Lit::Int(_),
+                ..
+            })) if *option_type == OptionType::Integer => Some(lit.clone()),
+            _ => None,
+        };
+
+        let name = (args.value_from_name() && *option_type == OptionType::String)
+            .then(|| Lit::Str(self.name()));
+
+        let value = self
+            .value
+            .clone()
+            .or(discriminant)
+            .or(name)
+            .unwrap_or_else(|| {
+                let ident_s = self.ident.to_string();
+                Lit::Str(LitStr::new(
+                    &args
+                        .rename_rule()
+                        .apply(ident_s.strip_prefix("r#").unwrap_or(&ident_s)),
+                    self.ident.span(),
+                ))
+            });
+
+        match value {
+            Lit::Int(int) if *option_type == OptionType::Number => Lit::Float(LitFloat::new(
+                &format!("{}.0", int.base10_digits()),
+                int.span(),
+            )),
+            value => value,
+        }
+    }
+
+    fn add_option_choice(&self, method_name: &Ident, args: &Args) -> TokenStream {
         let name = self.name();
-        let value = self.value();
+        let value = self.value(args);
+        let builder_methods = &self.builder;
 
-        quote!(#name, #value)
+        quote! {
+            .#method_name(#name, #value)
+            #builder_methods
+        }
     }
 
     #[allow(clippy::wrong_self_convention)]
-    fn from_value(&self) -> TokenStream {
-        let value = self.value();
+    fn from_value(&self, args: &Args) -> TokenStream {
+        let value = self.value(args);
         let ident = &self.ident;
 
-        quote! {
-            #value => ::std::result::Result::Ok(Self::#ident),
+        if *args.option_type == OptionType::Number {
+            quote! {
+                if matches(#value) {
+                    return ::std::result::Result::Ok(Self::#ident);
+                }
+            }
+        } else {
+            quote! {
+                #value => ::std::result::Result::Ok(Self::#ident),
+            }
         }
     }
 }
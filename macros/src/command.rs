@@ -1,13 +1,17 @@
 use darling::{
     ast::{Data, Style},
     error::Accumulator,
+    util::Flag,
     Error, FromDeriveInput,
 };
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{Generics, Ident};
+use syn::{parse_quote, Generics, Ident, LitStr, Path};
 
-use crate::{BuilderMethodList, Field, Variant};
+use crate::{
+    option_bounds, option_fields, validate_unique_names, variant_option_fields, with_bounds,
+    BuilderMethodList, CratePaths, Field, Variant,
+};
 
 #[derive(Debug, FromDeriveInput)]
 #[darling(
@@ -27,19 +31,29 @@ pub struct Args {
     data: Data<Variant, Field>,
 
     builder: Option<BuilderMethodList>,
+    default_description: Option<LitStr>,
+    option_description_prefix: Option<String>,
+
+    collect_errors: Flag,
+
+    serenity: Option<Path>,
+    serenity_commands: Option<Path>,
 }
 
 impl Args {
-    fn create_command(&self, acc: &mut Accumulator) -> TokenStream {
+    fn create_command(&self, paths: &CratePaths, acc: &mut Accumulator) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
         let body = match &self.data {
             Data::Struct(fields) => match fields.style {
                 Style::Struct => {
-                    let options = fields.fields.iter().map(|field| field.create_option(acc));
+                    let prefix = self.option_description_prefix.as_deref();
+                    let options = Field::create_options(&fields.fields, prefix, paths, acc);
 
                     quote! {
-                        ::serenity::all::CreateCommand::new(name)
+                        #serenity::all::CreateCommand::new(name)
                             .description(description)
-                            .set_options(::std::vec![#(#options),*])
+                            .set_options(#options)
                     }
                 }
                 Style::Tuple => {
@@ -50,23 +64,36 @@ impl Args {
                     let ty = &field.ty;
 
                     quote! {
-                        <#ty as ::serenity_commands::Command>::create_command(name, description)
+                        <#ty as #serenity_commands::Command>::create_command(name, description)
                     }
                 }
                 Style::Unit => {
                     quote! {
-                        ::serenity::all::CreateCommand::new(name)
+                        #serenity::all::CreateCommand::new(name)
                             .description(description)
                     }
                 }
             },
             Data::Enum(variants) => {
-                let options = variants
-                    .iter()
-                    .map(|variant| variant.create_sub_command_or_group(acc));
+                validate_unique_names(
+                    variants
+                        .iter()
+                        .map(|variant| (variant.name(), &variant.ident)),
+                    acc,
+                );
+
+                let sub_command_group = parse_quote!(#serenity_commands::SubCommandGroup);
+                let options = variants.iter().map(|variant| {
+                    variant.create_sub_command_or_group(
+                        &sub_command_group,
+                        self.default_description.as_ref(),
+                        paths,
+                        acc,
+                    )
+                });
 
                 quote! {
-                    ::serenity::all::CreateCommand::new(name)
+                    #serenity::all::CreateCommand::new(name)
                         .description(description)
                         .set_options(::std::vec![#(#options),*])
                 }
@@ -79,7 +106,7 @@ impl Args {
             fn create_command(
                 name: impl ::std::convert::Into<::std::string::String>,
                 description: impl ::std::convert::Into<::std::string::String>,
-            ) -> ::serenity::all::CreateCommand {
+            ) -> #serenity::all::CreateCommand {
                 #body
                 #builder_methods
             }
@@ -87,20 +114,17 @@ impl Args {
     }
 
     #[allow(clippy::wrong_self_convention)]
-    fn from_options(&self) -> TokenStream {
+    fn from_options(&self, paths: &CratePaths) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
         let body = match &self.data {
             Data::Struct(fields) => match fields.style {
-                Style::Struct => {
-                    let (fold, inits) = Field::from_options(&fields.fields);
-
-                    quote! {
-                        #fold
-
-                        ::std::result::Result::Ok(Self {
-                            #(#inits),*
-                        })
-                    }
-                }
+                Style::Struct => Field::from_options(
+                    &fields.fields,
+                    &quote!(Self),
+                    self.collect_errors.is_present(),
+                    paths,
+                ),
                 Style::Tuple => {
                     let field = fields
                         .fields
@@ -109,7 +133,10 @@ impl Args {
                     let ty = &field.ty;
 
                     quote! {
-                        <#ty as ::serenity_commands::Command>::from_command(options)
+                        <#ty as #serenity_commands::Command>::from_options_resolved(
+                            options,
+                            resolved,
+                        )
                             .map(Self)
                     }
                 }
@@ -120,20 +147,33 @@ impl Args {
                 }
             },
             Data::Enum(variants) => {
-                let arms = variants.iter().map(Variant::from_subcommand_or_group_value);
+                let sub_command_group = parse_quote!(#serenity_commands::SubCommandGroup);
+                let arms = variants.iter().map(|variant| {
+                    variant.from_subcommand_or_group_value(
+                        &sub_command_group,
+                        self.collect_errors.is_present(),
+                        paths,
+                    )
+                });
 
                 quote! {
-                    let [option] = options else {
-                        return ::std::result::Result::Err(::serenity_commands::Error::IncorrectCommandOptionCount {
-                            got: options.len(),
-                            expected: 1,
-                        });
+                    let option = match options {
+                        [option] => option,
+                        [] => return ::std::result::Result::Err(
+                            #serenity_commands::Error::MissingSubCommand
+                        ),
+                        _ => return ::std::result::Result::Err(
+                            #serenity_commands::Error::IncorrectCommandOptionCount {
+                                got: options.len(),
+                                expected: 1,
+                            }
+                        ),
                     };
 
                     match option.name.as_str() {
                         #(#arms,)*
                         unknown => ::std::result::Result::Err(
-                            ::serenity_commands::Error::UnknownCommandOption(
+                            #serenity_commands::Error::UnknownCommandOption(
                                 ::std::borrow::ToOwned::to_owned(unknown)
                             )
                         ),
@@ -144,8 +184,18 @@ impl Args {
 
         quote! {
             fn from_options(
-                options: &[::serenity::all::CommandDataOption],
-            ) -> ::serenity_commands::Result<Self> {
+                options: &[#serenity::all::CommandDataOption],
+            ) -> #serenity_commands::Result<Self> {
+                <Self as #serenity_commands::Command>::from_options_resolved(
+                    options,
+                    &::std::default::Default::default(),
+                )
+            }
+
+            fn from_options_resolved(
+                options: &[#serenity::all::CommandDataOption],
+                resolved: &#serenity::all::CommandDataResolved,
+            ) -> #serenity_commands::Result<Self> {
                 #body
             }
         }
@@ -157,15 +207,26 @@ impl ToTokens for Args {
         let mut acc = Error::accumulator();
 
         let ident = &self.ident;
+        let paths = CratePaths::new(self.serenity.as_ref(), self.serenity_commands.as_ref());
+        let serenity_commands = &paths.serenity_commands;
 
-        let create_command = self.create_command(&mut acc);
-        let from_options = self.from_options();
+        let create_command = self.create_command(&paths, &mut acc);
+        let from_options = self.from_options(&paths);
 
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let generics = match &self.data {
+            Data::Struct(fields) => {
+                with_bounds(&self.generics, option_bounds(option_fields(fields), &paths))
+            }
+            Data::Enum(variants) => with_bounds(
+                &self.generics,
+                option_bounds(variant_option_fields(variants), &paths),
+            ),
+        };
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
         let implementation = quote! {
             #[automatically_derived]
-            impl #impl_generics ::serenity_commands::Command for #ident #ty_generics #where_clause {
+            impl #impl_generics #serenity_commands::Command for #ident #ty_generics #where_clause {
                 #create_command
 
                 #from_options
@@ -1,62 +1,399 @@
-use darling::{ast::Data, error::Accumulator, util::Ignored, Error, FromDeriveInput};
+use darling::{
+    ast::{Data, Style},
+    error::Accumulator,
+    util::{Flag, SpannedValue},
+    Error, FromDeriveInput,
+};
+use heck::ToSnakeCase;
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
-use syn::{Generics, Ident};
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_quote, Attribute, Generics, Ident, Path, Visibility};
 
-use crate::Variant;
+use crate::{
+    option_bounds, validate_unique_names, variant_option_fields, with_bounds, BuilderMethodList,
+    CratePaths, Field, InstallationContextList, Variant,
+};
 
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(command), supports(enum_named, enum_newtype, enum_unit))]
+#[darling(
+    attributes(command),
+    forward_attrs(doc),
+    supports(
+        struct_named,
+        struct_newtype,
+        struct_unit,
+        enum_named,
+        enum_newtype,
+        enum_unit,
+    )
+)]
 pub struct Args {
     ident: Ident,
+    vis: Visibility,
     generics: Generics,
-    data: Data<Variant, Ignored>,
+    data: Data<Variant, Field>,
+    attrs: Vec<Attribute>,
+
+    name: Option<SpannedValue<String>>,
+    verbatim: Flag,
+    builder: Option<BuilderMethodList>,
+    prefix: Option<SpannedValue<String>>,
+
+    option_description_prefix: Option<String>,
+
+    nsfw: Flag,
+    integration_types: Option<SpannedValue<InstallationContextList>>,
+    guild_only: Flag,
+
+    dispatch: Flag,
+    try_from: Flag,
+    collect_errors: Flag,
+
+    serenity: Option<Path>,
+    serenity_commands: Option<Path>,
 }
 
 impl Args {
-    fn create_commands(&self, acc: &mut Accumulator) -> TokenStream {
-        let commands = self
-            .data
-            .as_ref()
-            .take_enum()
-            .expect("`Args` should only accept `enum`s")
+    /// The top-level commands, treating a struct as a single command.
+    fn variants(&self) -> Vec<Variant> {
+        match &self.data {
+            Data::Enum(variants) => variants.clone(),
+            Data::Struct(fields) => vec![Variant {
+                ident: self.ident.clone(),
+                fields: fields.clone(),
+                attrs: self.attrs.clone(),
+                name: self.name.clone(),
+                verbatim: self.verbatim,
+                builder: self.builder.clone(),
+                option_description_prefix: self.option_description_prefix.clone(),
+                nsfw: self.nsfw,
+                integration_types: self.integration_types.clone(),
+                other: Flag::default(),
+            }],
+        }
+    }
+
+    /// The `prefix` to prepend to the names of commands without an explicit
+    /// `name`.
+    fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref().map(String::as_str)
+    }
+
+    fn forbid_enum_attributes(&self, acc: &mut Accumulator) {
+        if !self.data.is_enum() {
+            return;
+        }
+
+        let attributes = [
+            ("name", self.name.is_some()),
+            ("verbatim", self.verbatim.is_present()),
+            ("builder", self.builder.is_some()),
+            (
+                "option_description_prefix",
+                self.option_description_prefix.is_some(),
+            ),
+            ("nsfw", self.nsfw.is_present()),
+            ("integration_types", self.integration_types.is_some()),
+        ];
+
+        for (attribute, present) in attributes {
+            if present {
+                acc.push(
+                    Error::custom(format!(
+                        "`{attribute}` can only be used on `struct`s; use it on each variant instead"
+                    ))
+                    .with_span(&self.ident),
+                );
+            }
+        }
+    }
+
+    /// The variant marked with `#[command(other)]`, if any.
+    fn other_variant(&self) -> Option<&Variant> {
+        let Data::Enum(variants) = &self.data else {
+            return None;
+        };
+
+        variants.iter().find(|variant| variant.other.is_present())
+    }
+
+    fn validate_other_variants(&self, acc: &mut Accumulator) {
+        let Data::Enum(variants) = &self.data else {
+            return;
+        };
+
+        let others = variants.iter().filter(|variant| variant.other.is_present());
+
+        for (idx, variant) in others.enumerate() {
+            if !matches!(variant.fields.style, Style::Tuple) {
+                acc.push(
+                    Error::custom(
+                        "`other` can only be used on newtype variants containing `CommandData`",
+                    )
+                    .with_span(&variant.other.span()),
+                );
+            }
+
+            if idx > 0 {
+                acc.push(
+                    Error::custom("only one variant can be marked with `other`")
+                        .with_span(&variant.other.span()),
+                );
+            }
+
+            let attributes = [
+                ("name", variant.name.is_some()),
+                ("verbatim", variant.verbatim.is_present()),
+                ("builder", variant.builder.is_some()),
+                (
+                    "option_description_prefix",
+                    variant.option_description_prefix.is_some(),
+                ),
+                ("nsfw", variant.nsfw.is_present()),
+                ("integration_types", variant.integration_types.is_some()),
+            ];
+
+            for (attribute, present) in attributes {
+                if present {
+                    acc.push(
+                        Error::custom(format!(
+                            "`{attribute}` cannot be used with `other`, as the variant is not \
+                             created as a command"
+                        ))
+                        .with_span(&variant.other.span()),
+                    );
+                }
+            }
+        }
+    }
+
+    fn create_commands(&self, paths: &CratePaths, acc: &mut Accumulator) -> TokenStream {
+        let serenity = &paths.serenity;
+        let variants = self
+            .variants()
             .into_iter()
-            .map(|variant| variant.create_command(acc));
+            .filter(|variant| !variant.other.is_present())
+            .collect::<Vec<_>>();
+
+        validate_unique_names(
+            variants
+                .iter()
+                .map(|variant| (variant.command_name(self.prefix()), &variant.ident)),
+            acc,
+        );
+
+        let commands = variants
+            .iter()
+            .map(|variant| variant.create_command(self.prefix(), self.guild_only, paths, acc))
+            .collect::<Vec<_>>();
+
+        let count = commands.len();
+        let names = variants
+            .iter()
+            .map(|variant| variant.command_name(self.prefix()));
 
         quote! {
-            fn create_commands() -> ::std::vec::Vec<::serenity::all::CreateCommand> {
-                ::std::vec![#(#commands),*]
+            const COMMAND_NAMES: &'static [&'static str] = &[#(#names),*];
+
+            fn create_commands_iter() -> impl ::std::iter::Iterator<
+                Item = #serenity::all::CreateCommand
+            > {
+                let commands: [#serenity::all::CreateCommand; #count] = [#(#commands),*];
+
+                ::std::iter::IntoIterator::into_iter(commands)
             }
         }
     }
 
     #[allow(clippy::wrong_self_convention)]
-    fn from_command_data(&self) -> TokenStream {
-        let variants = self
-            .data
-            .as_ref()
-            .take_enum()
-            .expect("`Args` should only accept `enum`s");
+    fn from_command_data(&self, paths: &CratePaths) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
+        let arms = match &self.data {
+            Data::Enum(_) => self
+                .variants()
+                .iter()
+                .filter(|variant| !variant.other.is_present())
+                .map(|variant| {
+                    variant.from_command_options(
+                        self.prefix(),
+                        self.collect_errors.is_present(),
+                        paths,
+                    )
+                })
+                .collect::<Vec<_>>(),
+            Data::Struct(_) => self
+                .variants()
+                .iter()
+                .map(|variant| {
+                    variant.from_command_options_as(
+                        &quote!(Self),
+                        self.prefix(),
+                        self.collect_errors.is_present(),
+                        paths,
+                    )
+                })
+                .collect(),
+        };
+
+        let fallback_arm = self.other_variant().map_or_else(
+            || {
+                quote! {
+                    unknown => ::std::result::Result::Err(
+                        #serenity_commands::Error::UnknownCommand(
+                            ::std::borrow::ToOwned::to_owned(unknown)
+                        )
+                    )
+                }
+            },
+            |other| {
+                let ident = &other.ident;
 
-        let arms = variants.into_iter().map(Variant::from_command_options);
+                quote! {
+                    _ => ::std::result::Result::Ok(
+                        Self::#ident(::std::clone::Clone::clone(data))
+                    )
+                }
+            },
+        );
 
         quote! {
             fn from_command_data(
-                data: &::serenity::all::CommandData
-            ) -> ::serenity_commands::Result<Self> {
+                data: &#serenity::all::CommandData
+            ) -> #serenity_commands::Result<Self> {
                 let options = &data.options;
+                let resolved = &data.resolved;
 
                 match data.name.as_str() {
                     #(#arms,)*
-                    unknown => ::std::result::Result::Err(
-                        ::serenity_commands::Error::UnknownCommand(
-                            ::std::borrow::ToOwned::to_owned(unknown)
-                        )
-                    ),
+                    #fallback_arm,
                 }
             }
         }
     }
+
+    /// Generate the `<Ident>Run` trait, with a method for each command, and
+    /// a `run` method dispatching to it.
+    fn dispatch(&self, acc: &mut Accumulator) -> Option<TokenStream> {
+        if !self.dispatch.is_present() {
+            return None;
+        }
+
+        let Data::Enum(variants) = &self.data else {
+            acc.push(
+                Error::custom("`dispatch` can only be used on `enum`s")
+                    .with_span(&self.dispatch.span()),
+            );
+            return None;
+        };
+
+        if !self.generics.params.is_empty() {
+            acc.push(
+                Error::custom("`dispatch` cannot be used on generic `enum`s")
+                    .with_span(&self.dispatch.span()),
+            );
+            return None;
+        }
+
+        let ident = &self.ident;
+        let vis = &self.vis;
+        let trait_ident = format_ident!("{ident}Run");
+
+        let (methods, arms): (Vec<_>, Vec<_>) = variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let docs = &variant.attrs;
+                let method = format_ident!(
+                    "{}",
+                    variant_ident.to_string().to_snake_case(),
+                    span = variant_ident.span()
+                );
+
+                let (params, pattern, args) = match variant.fields.style {
+                    Style::Struct => {
+                        let idents = variant.fields.iter().map(Field::ident).collect::<Vec<_>>();
+                        let tys = variant.fields.iter().map(|field| &field.ty);
+
+                        (
+                            quote!(#(#idents: #tys),*),
+                            quote!(Self::#variant_ident { #(#idents),* }),
+                            quote!(#(#idents),*),
+                        )
+                    }
+                    Style::Tuple => {
+                        let ty = &variant
+                            .fields
+                            .fields
+                            .first()
+                            .expect(
+                                "`Args` should only accept tuple `enum` variants with one field",
+                            )
+                            .ty;
+
+                        (
+                            quote!(command: #ty),
+                            quote!(Self::#variant_ident(command)),
+                            quote!(command),
+                        )
+                    }
+                    Style::Unit => (quote!(), quote!(Self::#variant_ident), quote!()),
+                };
+
+                let method_def = quote! {
+                    #(#docs)*
+                    fn #method(
+                        &self,
+                        #params
+                    ) -> impl ::std::future::Future<Output = Self::Output> + ::std::marker::Send;
+                };
+
+                let arm = quote! {
+                    #pattern => handler.#method(#args).await
+                };
+
+                (method_def, arm)
+            })
+            .unzip();
+
+        let trait_doc = format!("Handlers for each command in [`{ident}`].");
+
+        Some(quote! {
+            #[doc = #trait_doc]
+            #vis trait #trait_ident: ::std::marker::Sync {
+                /// The output of each handler.
+                type Output;
+
+                #(#methods)*
+            }
+
+            impl #ident {
+                /// Run the method of `handler` corresponding to this command.
+                #vis async fn run<H: #trait_ident + ?::std::marker::Sized>(
+                    self,
+                    handler: &H,
+                ) -> H::Output {
+                    match self {
+                        #(#arms,)*
+                    }
+                }
+            }
+        })
+    }
+
+    /// The inherent `NAME` constant of a `struct`, which is a single command.
+    fn name_constant(&self) -> Option<TokenStream> {
+        let Data::Struct(_) = &self.data else {
+            return None;
+        };
+
+        let name = self.variants()[0].command_name(self.prefix());
+
+        Some(quote! {
+            /// The name of the command.
+            pub const NAME: &'static str = #name;
+        })
+    }
 }
 
 impl ToTokens for Args {
@@ -64,19 +401,68 @@ impl ToTokens for Args {
         let mut acc = Error::accumulator();
 
         let ident = &self.ident;
+        let paths = CratePaths::new(self.serenity.as_ref(), self.serenity_commands.as_ref());
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
 
-        let create_commands = self.create_commands(&mut acc);
-        let from_command_data = self.from_command_data();
+        self.forbid_enum_attributes(&mut acc);
+        self.validate_other_variants(&mut acc);
 
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let create_commands = self.create_commands(&paths, &mut acc);
+        let from_command_data = self.from_command_data(&paths);
+        let dispatch = self.dispatch(&mut acc);
+
+        let variants = self.variants();
+        let command_bounds = variants
+            .iter()
+            .filter(|variant| matches!(variant.fields.style, Style::Tuple))
+            .flat_map(|variant| &variant.fields.fields)
+            .map(|field| (&field.ty, parse_quote!(#serenity_commands::Command)));
+        let generics = with_bounds(
+            &self.generics,
+            option_bounds(variant_option_fields(&variants), &paths).chain(command_bounds),
+        );
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let name_constant = self.name_constant().map(|constant| {
+            quote! {
+                #[automatically_derived]
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    #constant
+                }
+            }
+        });
+
+        let try_from = self.try_from.is_present().then(|| {
+            quote! {
+                #[automatically_derived]
+                impl #impl_generics ::std::convert::TryFrom<&#serenity::all::CommandData>
+                    for #ident #ty_generics #where_clause
+                {
+                    type Error = #serenity_commands::Error;
+
+                    fn try_from(
+                        data: &#serenity::all::CommandData,
+                    ) -> #serenity_commands::Result<Self> {
+                        <Self as #serenity_commands::Commands>::from_command_data(data)
+                    }
+                }
+            }
+        });
 
         let implementation = quote! {
             #[automatically_derived]
-            impl #impl_generics ::serenity_commands::Commands for #ident #ty_generics #where_clause {
+            impl #impl_generics #serenity_commands::Commands for #ident #ty_generics #where_clause {
                 #create_commands
 
                 #from_command_data
             }
+
+            #name_constant
+
+            #try_from
+
+            #dispatch
         };
 
         acc.finish_with(implementation)
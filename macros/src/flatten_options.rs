@@ -0,0 +1,106 @@
+use darling::{
+    ast::{Data, Fields, Style},
+    error::Accumulator,
+    util::{Flag, Ignored},
+    Error, FromDeriveInput,
+};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Generics, Ident, Path};
+
+use crate::{option_bounds, option_fields, with_bounds, CratePaths, Field};
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(command), supports(struct_named, struct_unit))]
+pub struct Args {
+    ident: Ident,
+    generics: Generics,
+    data: Data<Ignored, Field>,
+
+    option_description_prefix: Option<String>,
+
+    collect_errors: Flag,
+
+    serenity: Option<Path>,
+    serenity_commands: Option<Path>,
+}
+
+/// Generate the `FlattenOptions` implementation for the named or unit struct
+/// `ident` with the given `fields`.
+pub fn implementation(
+    ident: &Ident,
+    generics: &Generics,
+    fields: &Fields<Field>,
+    prefix: Option<&str>,
+    collect_errors: bool,
+    paths: &CratePaths,
+    acc: &mut Accumulator,
+) -> TokenStream {
+    let serenity = &paths.serenity;
+    let serenity_commands = &paths.serenity_commands;
+
+    let (create_options, from_options) = match fields.style {
+        Style::Struct => (
+            Field::create_options(&fields.fields, prefix, paths, acc),
+            Field::from_options(&fields.fields, &quote!(Self), collect_errors, paths),
+        ),
+        Style::Tuple => unreachable!("tuple structs cannot be flattened"),
+        Style::Unit => (
+            quote!(::std::vec::Vec::new()),
+            quote!(::std::result::Result::Ok(Self)),
+        ),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #serenity_commands::FlattenOptions for #ident #ty_generics #where_clause {
+            fn create_options() -> ::std::vec::Vec<#serenity::all::CreateCommandOption> {
+                #create_options
+            }
+
+            fn from_options(
+                options: &[#serenity::all::CommandDataOption],
+            ) -> #serenity_commands::Result<Self> {
+                <Self as #serenity_commands::FlattenOptions>::from_options_resolved(
+                    options,
+                    &::std::default::Default::default(),
+                )
+            }
+
+            fn from_options_resolved(
+                options: &[#serenity::all::CommandDataOption],
+                resolved: &#serenity::all::CommandDataResolved,
+            ) -> #serenity_commands::Result<Self> {
+                #from_options
+            }
+        }
+    }
+}
+
+impl ToTokens for Args {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let mut acc = Error::accumulator();
+
+        let Data::Struct(fields) = &self.data else {
+            unreachable!()
+        };
+
+        let paths = CratePaths::new(self.serenity.as_ref(), self.serenity_commands.as_ref());
+        let generics = with_bounds(&self.generics, option_bounds(option_fields(fields), &paths));
+        let implementation = implementation(
+            &self.ident,
+            &generics,
+            fields,
+            self.option_description_prefix.as_deref(),
+            self.collect_errors.is_present(),
+            &paths,
+            &mut acc,
+        );
+
+        acc.finish_with(implementation)
+            .unwrap_or_else(Error::write_errors)
+            .to_tokens(tokens);
+    }
+}
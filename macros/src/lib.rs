@@ -6,31 +6,33 @@
 mod basic_option;
 mod command;
 mod commands;
+mod flatten_options;
 mod sub_command;
 mod sub_command_group;
 
-use std::iter;
+use std::{collections::HashSet, iter};
 
 use darling::{
     ast::{Fields, NestedMeta, Style},
     error::Accumulator,
-    util::SpannedValue,
+    util::{Flag, Override, SpannedValue},
     Error, FromDeriveInput, FromField, FromMeta, FromVariant,
 };
-use heck::ToKebabCase;
-use proc_macro2::{Span, TokenStream};
+use heck::{ToKebabCase, ToTitleCase};
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned, ToTokens};
 use syn::{
     parse::{Parse, Parser},
-    parse_macro_input,
+    parse_macro_input, parse_quote,
     punctuated::Punctuated,
     spanned::Spanned,
     token::Paren,
-    Attribute, Expr, ExprLit, Ident, Index, Lit, LitStr, MacroDelimiter, Meta, MetaNameValue,
-    Token, Type,
+    Attribute, Expr, ExprLit, ExprUnary, GenericArgument, Generics, Ident, Index, Lit, LitStr,
+    MacroDelimiter, Meta, MetaNameValue, Path, PathArguments, Token, Type, TypeParamBound,
+    TypePath, UnOp, WherePredicate,
 };
 
-#[derive(Debug, FromVariant)]
+#[derive(Debug, Clone, FromVariant)]
 #[darling(attributes(command), forward_attrs(doc))]
 struct Variant {
     ident: Ident,
@@ -38,71 +40,87 @@ struct Variant {
     attrs: Vec<Attribute>,
 
     name: Option<SpannedValue<String>>,
+    verbatim: Flag,
     builder: Option<BuilderMethodList>,
+
+    option_description_prefix: Option<String>,
+
+    nsfw: Flag,
+    integration_types: Option<SpannedValue<InstallationContextList>>,
+
+    other: Flag,
 }
 
 impl Variant {
     fn name(&self) -> LitStr {
-        option_name(&self.ident, self.name.as_ref())
+        option_name(&self.ident, self.name.as_ref(), self.verbatim.is_present())
     }
 
-    fn create_command(&self, acc: &mut Accumulator) -> TokenStream {
+    /// The name of this variant as a top-level command, with `prefix`
+    /// prepended unless the name is given explicitly.
+    fn command_name(&self, prefix: Option<&str>) -> LitStr {
         let name = self.name();
-        let description = documentation_string(&self.attrs, &self.ident, acc);
-
-        let body = match self.fields.style {
-            Style::Struct => {
-                let fields = self.fields.iter().map(|field| field.create_option(acc));
 
-                quote! {
-                    ::serenity::all::CreateCommand::new(#name)
-                        .description(#description)
-                        .set_options(::std::vec![#(#fields),*])
-                }
+        match prefix {
+            Some(prefix) if self.name.is_none() => {
+                LitStr::new(&format!("{prefix}{}", name.value()), name.span())
             }
-            Style::Tuple => {
-                let field = self
-                    .fields
-                    .fields
-                    .first()
-                    .expect("`Args` should only accept tuple `enum` variants with one field");
-                let ty = &field.ty;
+            _ => name,
+        }
+    }
 
-                quote! {
-                    <#ty as ::serenity_commands::Command>::create_command(#name, #description)
-                }
-            }
-            Style::Unit => {
-                quote! {
-                    ::serenity::all::CreateCommand::new(#name)
-                        .description(#description)
-                }
-            }
-        };
+    fn forbid_top_level_attributes(&self, acc: &mut Accumulator) {
+        if self.nsfw.is_present() {
+            acc.push(
+                Error::custom("`nsfw` can only be used on top-level commands")
+                    .with_span(&self.nsfw.span()),
+            );
+        }
 
-        let builder_methods = &self.builder;
+        if let Some(integration_types) = &self.integration_types {
+            acc.push(
+                Error::custom("`integration_types` can only be used on top-level commands")
+                    .with_span(&integration_types.span()),
+            );
+        }
 
-        quote! {
-            #body
-            #builder_methods
+        if self.other.is_present() {
+            acc.push(
+                Error::custom("`other` can only be used on top-level commands")
+                    .with_span(&self.other.span()),
+            );
         }
     }
 
-    fn create_sub_command_or_group(&self, acc: &mut Accumulator) -> TokenStream {
-        let name = self.name();
-        let description = documentation_string(&self.attrs, &self.ident, acc);
+    /// Generate the `CreateCommand` for this variant, named with `prefix` and
+    /// restricted to guilds if `guild_only` is present. As it is applied
+    /// before the variant's `builder`, this can be overridden per variant.
+    fn create_command(
+        &self,
+        prefix: Option<&str>,
+        guild_only: Flag,
+        paths: &CratePaths,
+        acc: &mut Accumulator,
+    ) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
+        let name = self.command_name(prefix);
+        validate_name(
+            &name,
+            self.verbatim.is_present() && self.name.is_none(),
+            acc,
+        );
+        let description = documentation_string(&self.attrs, &self.ident, None, acc);
 
         let body = match self.fields.style {
             Style::Struct => {
-                let fields = self.fields.iter().map(|field| field.create_option(acc));
+                let prefix = self.option_description_prefix.as_deref();
+                let options = Field::create_options(&self.fields.fields, prefix, paths, acc);
 
                 quote! {
-                    ::serenity::all::CreateCommandOption::new(
-                        ::serenity::all::CommandOptionType::SubCommand,
-                        #name,
-                        #description,
-                    )
-                    #(.add_sub_option(#fields))*
+                    #serenity::all::CreateCommand::new(#name)
+                        .description(#description)
+                        .set_options(#options)
                 }
             }
             Style::Tuple => {
@@ -114,46 +132,75 @@ impl Variant {
                 let ty = &field.ty;
 
                 quote! {
-                    <#ty as ::serenity_commands::SubCommandGroup>::create_option(
-                        #name,
-                        #description,
-                    )
+                    <#ty as #serenity_commands::Command>::create_command(#name, #description)
                 }
             }
             Style::Unit => {
                 quote! {
-                    ::serenity::all::CreateCommandOption::new(
-                        ::serenity::all::CommandOptionType::SubCommand,
-                        #name,
-                        #description,
-                    )
+                    #serenity::all::CreateCommand::new(#name)
+                        .description(#description)
                 }
             }
         };
 
+        let nsfw = self
+            .nsfw
+            .is_present()
+            .then(|| quote_spanned!(self.nsfw.span()=> .nsfw(true)));
+        let integration_types = self
+            .integration_types
+            .as_deref()
+            .map(|integration_types| integration_types.builder_call(paths));
+        let guild_only = guild_only.is_present().then(|| {
+            quote_spanned! {guild_only.span()=>
+                .contexts(::std::vec![#serenity::all::InteractionContext::Guild])
+            }
+        });
         let builder_methods = &self.builder;
 
         quote! {
             #body
+            #nsfw
+            #integration_types
+            #guild_only
             #builder_methods
         }
     }
 
-    fn create_sub_command(&self, acc: &mut Accumulator) -> TokenStream {
+    /// Create this variant as a sub-command, or as the option created by
+    /// `inner_trait` for a newtype variant.
+    fn create_sub_command_or_group(
+        &self,
+        inner_trait: &Path,
+        default_description: Option<&LitStr>,
+        paths: &CratePaths,
+        acc: &mut Accumulator,
+    ) -> TokenStream {
+        let serenity = &paths.serenity;
+
+        self.forbid_top_level_attributes(acc);
+
         let name = self.name();
-        let description = documentation_string(&self.attrs, &self.ident, acc);
+        validate_name(
+            &name,
+            self.verbatim.is_present() && self.name.is_none(),
+            acc,
+        );
+        let description =
+            documentation_string(&self.attrs, &self.ident, default_description.cloned(), acc);
 
         let body = match self.fields.style {
             Style::Struct => {
-                let fields = self.fields.iter().map(|field| field.create_option(acc));
+                let prefix = self.option_description_prefix.as_deref();
+                let options = Field::create_options(&self.fields.fields, prefix, paths, acc);
 
                 quote! {
-                    ::serenity::all::CreateCommandOption::new(
-                        ::serenity::all::CommandOptionType::SubCommand,
+                    #serenity::all::CreateCommandOption::new(
+                        #serenity::all::CommandOptionType::SubCommand,
                         #name,
                         #description,
                     )
-                    #(.add_sub_option(#fields))*
+                    .set_sub_options(#options)
                 }
             }
             Style::Tuple => {
@@ -164,8 +211,11 @@ impl Variant {
                     .expect("`Args` should only accept tuple `enum` variants with one field");
                 let ty = &field.ty;
 
-                quote! {
-                    <#ty as ::serenity_commands::SubCommand>::create_option(
+                // Spanned at the inner type, so that nesting a sub-command group
+                // inside of another points at the offending type rather than at
+                // the derive.
+                quote_spanned! {ty.span()=>
+                    <#ty as #inner_trait>::create_option(
                         #name,
                         #description,
                     )
@@ -173,8 +223,8 @@ impl Variant {
             }
             Style::Unit => {
                 quote! {
-                    ::serenity::all::CreateCommandOption::new(
-                        ::serenity::all::CommandOptionType::SubCommand,
+                    #serenity::all::CreateCommandOption::new(
+                        #serenity::all::CommandOptionType::SubCommand,
                         #name,
                         #description,
                     )
@@ -191,75 +241,31 @@ impl Variant {
     }
 
     #[allow(clippy::wrong_self_convention)]
-    fn from_command_options(&self) -> TokenStream {
+    fn from_command_options(
+        &self,
+        prefix: Option<&str>,
+        collect_errors: bool,
+        paths: &CratePaths,
+    ) -> TokenStream {
         let ident = &self.ident;
 
-        let match_body = match self.fields.style {
-            Style::Struct => {
-                let (fold, field_init) = Field::from_options(&self.fields.fields);
-
-                quote! {
-                    #fold
-
-                    ::std::result::Result::Ok(Self::#ident {
-                        #(#field_init),*
-                    })
-                }
-            }
-            Style::Tuple => {
-                let field = self
-                    .fields
-                    .fields
-                    .first()
-                    .expect("`Args` should only accept tuple `enum` variants with one field");
-                let ty = &field.ty;
-
-                quote! {
-                    <#ty as ::serenity_commands::Command>::from_options(
-                        options
-                    ).map(Self::#ident)
-                }
-            }
-            Style::Unit => {
-                quote! {
-                    ::std::result::Result::Ok(Self::#ident)
-                }
-            }
-        };
-
-        let name = self.name();
-
-        quote! {
-            #name => { #match_body }
-        }
+        self.from_command_options_as(&quote!(Self::#ident), prefix, collect_errors, paths)
     }
 
+    /// Like [`Variant::from_command_options`], but constructs `path` rather
+    /// than the variant itself.
     #[allow(clippy::wrong_self_convention)]
-    fn from_subcommand_or_group_value(&self) -> TokenStream {
-        let ident = &self.ident;
+    fn from_command_options_as(
+        &self,
+        path: &TokenStream,
+        prefix: Option<&str>,
+        collect_errors: bool,
+        paths: &CratePaths,
+    ) -> TokenStream {
+        let serenity_commands = &paths.serenity_commands;
 
         let match_body = match self.fields.style {
-            Style::Struct => {
-                let (fold, field_init) = Field::from_options(&self.fields.fields);
-
-                quote! {
-                    let ::serenity::all::CommandDataOption {
-                        value: ::serenity::all::CommandDataOptionValue::SubCommand(options),
-                        ..
-                    } = option else {
-                        return ::std::result::Result::Err(::serenity_commands::Error::IncorrectCommandOptionType {
-                            got: option.kind(),
-                            expected: ::serenity::all::CommandOptionType::SubCommand,
-                        });
-                    };
-
-                    #fold
-
-                    ::std::result::Result::Ok(Self::#ident {
-                        #(#field_init),*
-                    })
-                }
-            }
+            Style::Struct => Field::from_options(&self.fields.fields, path, collect_errors, paths),
             Style::Tuple => {
                 let field = self
                     .fields
@@ -269,49 +275,63 @@ impl Variant {
                 let ty = &field.ty;
 
                 quote! {
-                    <#ty as ::serenity_commands::SubCommandGroup>::from_value(
-                        &option.value
-                    ).map(Self::#ident)
+                    <#ty as #serenity_commands::Command>::from_options_resolved(
+                        options,
+                        resolved,
+                    ).map(#path)
                 }
             }
             Style::Unit => {
                 quote! {
-                    ::std::result::Result::Ok(Self::#ident)
+                    ::std::result::Result::Ok(#path)
                 }
             }
         };
 
-        let name = self.name();
+        let name = self.command_name(prefix);
 
         quote! {
             #name => { #match_body }
         }
     }
 
+    /// Extract this variant from a sub-command, or with `inner_trait` for a
+    /// newtype variant.
     #[allow(clippy::wrong_self_convention)]
-    fn from_subcommand_value(&self) -> TokenStream {
+    fn from_subcommand_or_group_value(
+        &self,
+        inner_trait: &Path,
+        collect_errors: bool,
+        paths: &CratePaths,
+    ) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
         let ident = &self.ident;
 
         let match_body = match self.fields.style {
             Style::Struct => {
-                let (fold, field_init) = Field::from_options(&self.fields.fields);
+                let body = Field::from_options(
+                    &self.fields.fields,
+                    &quote!(Self::#ident),
+                    collect_errors,
+                    paths,
+                );
 
                 quote! {
-                    let ::serenity::all::CommandDataOption {
-                        value: ::serenity::all::CommandDataOptionValue::SubCommand(options),
+                    let #serenity::all::CommandDataOption {
+                        value: #serenity::all::CommandDataOptionValue::SubCommand(options),
                         ..
                     } = option else {
-                        return ::std::result::Result::Err(::serenity_commands::Error::IncorrectCommandOptionType {
-                            got: value.kind(),
-                            expected: ::serenity::all::CommandOptionType::SubCommand,
+                        return ::std::result::Result::Err(#serenity_commands::Error::IncorrectCommandOptionType {
+                            name: ::std::option::Option::Some(
+                                ::std::clone::Clone::clone(&option.name)
+                            ),
+                            got: option.kind(),
+                            expected: #serenity::all::CommandOptionType::SubCommand,
                         });
                     };
 
-                    #fold
-
-                    ::std::result::Result::Ok(Self::#ident {
-                        #(#field_init),*
-                    })
+                    #body
                 }
             }
             Style::Tuple => {
@@ -322,9 +342,10 @@ impl Variant {
                     .expect("`Args` should only accept tuple `enum` variants with one field");
                 let ty = &field.ty;
 
-                quote! {
-                    <#ty as ::serenity_commands::SubCommand>::from_value(
-                        &option.value
+                quote_spanned! {ty.span()=>
+                    <#ty as #inner_trait>::from_value_resolved(
+                        &option.value,
+                        resolved,
                     ).map(Self::#ident)
                 }
             }
@@ -343,7 +364,7 @@ impl Variant {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct DetachedMethodCall {
     method: Ident,
     #[allow(dead_code)]
@@ -389,11 +410,78 @@ impl FromMeta for DetachedMethodCall {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct BuilderMethodList {
     methods: Vec<DetachedMethodCall>,
 }
 
+impl BuilderMethodList {
+    /// Whether `method` is called by any of the methods in the list.
+    fn calls(&self, method: &str) -> bool {
+        self.methods.iter().any(|call| call.method == method)
+    }
+
+    /// The numeric literal passed as the sole argument to `method`, if any.
+    fn literal_argument(&self, method: &str) -> Option<(f64, &Expr)> {
+        self.methods
+            .iter()
+            .filter(|call| call.method == method && call.args.len() == 1)
+            .find_map(|call| {
+                let arg = call.args.first()?;
+
+                let (negative, lit) = match arg {
+                    Expr::Lit(ExprLit { lit, .. }) => (false, lit),
+                    Expr::Unary(ExprUnary {
+                        op: UnOp::Neg(_),
+                        expr,
+                        ..
+                    }) => match &**expr {
+                        Expr::Lit(ExprLit { lit, .. }) => (true, lit),
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+
+                let value = match lit {
+                    Lit::Int(int) => int.base10_parse::<f64>().ok()?,
+                    Lit::Float(float) => float.base10_parse::<f64>().ok()?,
+                    _ => return None,
+                };
+
+                Some((if negative { -value } else { value }, arg))
+            })
+    }
+
+    /// Check that literal bounds passed to the builder methods are not
+    /// inverted, and that literal maximum lengths are positive.
+    fn validate_bounds(&self, acc: &mut Accumulator) {
+        for (min, max) in [
+            ("min_int_value", "max_int_value"),
+            ("min_number_value", "max_number_value"),
+            ("min_length", "max_length"),
+        ] {
+            if let (Some((min_value, _)), Some((max_value, max_expr))) =
+                (self.literal_argument(min), self.literal_argument(max))
+            {
+                if min_value > max_value {
+                    acc.push(
+                        Error::custom(format!(
+                            "`{min}` ({min_value}) is greater than `{max}` ({max_value})"
+                        ))
+                        .with_span(max_expr),
+                    );
+                }
+            }
+        }
+
+        if let Some((max_length, max_expr)) = self.literal_argument("max_length") {
+            if max_length <= 0.0 {
+                acc.push(Error::custom("`max_length` must be positive").with_span(max_expr));
+            }
+        }
+    }
+}
+
 impl FromMeta for BuilderMethodList {
     fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
         let methods = items
@@ -401,7 +489,12 @@ impl FromMeta for BuilderMethodList {
             .map(DetachedMethodCall::from_nested_meta)
             .collect::<darling::Result<_>>()?;
 
-        Ok(Self { methods })
+        let list = Self { methods };
+
+        let mut acc = Error::accumulator();
+        list.validate_bounds(&mut acc);
+
+        acc.finish_with(list)
     }
 }
 
@@ -422,7 +515,100 @@ impl ToTokens for BuilderMethodList {
     }
 }
 
-#[derive(Debug, FromField)]
+#[derive(Debug, Clone)]
+struct InstallationContextList {
+    contexts: Vec<Ident>,
+}
+
+impl FromMeta for InstallationContextList {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut acc = Error::accumulator();
+
+        let contexts = items
+            .iter()
+            .filter_map(|item| {
+                let res = match item {
+                    NestedMeta::Meta(Meta::Path(path)) => path
+                        .get_ident()
+                        .filter(|ident| *ident == "Guild" || *ident == "User")
+                        .cloned()
+                        .ok_or_else(|| Error::custom("expected `Guild` or `User`").with_span(path)),
+                    NestedMeta::Meta(_) | NestedMeta::Lit(_) => {
+                        Err(Error::unsupported_format("non-path").with_span(item))
+                    }
+                };
+
+                acc.handle(res)
+            })
+            .collect();
+
+        acc.finish_with(Self { contexts })
+    }
+}
+
+impl InstallationContextList {
+    /// Generate the `integration_types` builder call for these contexts.
+    fn builder_call(&self, paths: &CratePaths) -> TokenStream {
+        let serenity = &paths.serenity;
+        let contexts = &self.contexts;
+
+        quote! {
+            .integration_types(::std::vec![
+                #(#serenity::all::InstallationContext::#contexts),*
+            ])
+        }
+    }
+}
+
+/// The type of a field's option, as overridden with
+/// `#[command(option_type = "...")]`.
+#[derive(Debug, Clone, Copy, FromMeta)]
+enum OptionType {
+    String,
+    Integer,
+    Boolean,
+    User,
+    Channel,
+    Role,
+    Mentionable,
+    Number,
+    Attachment,
+}
+
+impl ToTokens for OptionType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let variant = match self {
+            Self::String => quote!(String),
+            Self::Integer => quote!(Integer),
+            Self::Boolean => quote!(Boolean),
+            Self::User => quote!(User),
+            Self::Channel => quote!(Channel),
+            Self::Role => quote!(Role),
+            Self::Mentionable => quote!(Mentionable),
+            Self::Number => quote!(Number),
+            Self::Attachment => quote!(Attachment),
+        };
+
+        variant.to_tokens(tokens);
+    }
+}
+
+/// The value of a field's `default` attribute, which can be any expression
+/// rather than only a string literal.
+#[derive(Debug, Clone)]
+struct DefaultValue(Override<Expr>);
+
+impl FromMeta for DefaultValue {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self(Override::Inherit))
+    }
+
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        Ok(Self(Override::Explicit(expr.clone())))
+    }
+}
+
+#[derive(Debug, Clone, FromField)]
 #[darling(attributes(command), forward_attrs(doc))]
 struct Field {
     ident: Option<Ident>,
@@ -430,8 +616,21 @@ struct Field {
     attrs: Vec<Attribute>,
 
     name: Option<SpannedValue<String>>,
+    verbatim: Flag,
 
     builder: Option<BuilderMethodList>,
+
+    description_from_name: Flag,
+    choices_from: Option<Type>,
+    option_type: Option<SpannedValue<OptionType>>,
+    default: Option<DefaultValue>,
+    optional: Flag,
+
+    with_create: Option<Path>,
+    with_parse: Option<Path>,
+
+    flatten: Flag,
+    skip: Flag,
 }
 
 impl Field {
@@ -442,28 +641,386 @@ impl Field {
     }
 
     fn name(&self) -> LitStr {
-        option_name(self.ident(), self.name.as_ref())
+        option_name(self.ident(), self.name.as_ref(), self.verbatim.is_present())
+    }
+
+    /// Whether this field is created or extracted with its type's
+    /// `BasicOption` implementation.
+    fn uses_basic_option(&self) -> bool {
+        !(self.skip.is_present()
+            || self.flatten.is_present()
+            || (self.with_create.is_some() && self.with_parse.is_some()))
     }
 
-    fn create_option(&self, acc: &mut Accumulator) -> TokenStream {
+    fn create_option(
+        &self,
+        prefix: Option<&str>,
+        paths: &CratePaths,
+        acc: &mut Accumulator,
+    ) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
         let ident = self.ident();
         let ty = &self.ty;
 
         let name = self.name();
-        let description = documentation_string(&self.attrs, ident, acc);
+        validate_name(
+            &name,
+            self.verbatim.is_present() && self.name.is_none(),
+            acc,
+        );
+        let fallback = self
+            .description_from_name
+            .is_present()
+            .then(|| humanized_name(ident));
+        let mut description = documentation_string(&self.attrs, ident, fallback, acc);
+
+        if let Some(prefix) = prefix {
+            description = LitStr::new(
+                &format!("{} {}", prefix.trim_end(), description.value()),
+                description.span(),
+            );
+
+            if description.value().chars().count() > 100 {
+                acc.push(
+                    Error::custom("description with prefix exceeds 100 characters")
+                        .with_span(ident),
+                );
+            }
+        }
         let builder_methods = &self.builder;
 
+        let option = self.with_create.as_ref().map_or_else(
+            || {
+                quote! {
+                    <#ty as #serenity_commands::BasicOption>::create_option(
+                        #name,
+                        #description,
+                    )
+                }
+            },
+            |with_create| {
+                quote! {
+                    #with_create(#name, #description)
+                }
+            },
+        );
+
+        let option = match self.option_type.as_deref() {
+            Some(option_type) => {
+                quote!(#option.kind(#serenity::all::CommandOptionType::#option_type))
+            }
+            None => option,
+        };
+
+        let option = match &self.choices_from {
+            Some(choices_from) => quote! {
+                <#choices_from as #serenity_commands::ChoiceOption>::add_choices(#option)
+            },
+            None => option,
+        };
+
+        let sets_required = builder_methods
+            .as_ref()
+            .is_some_and(|builder| builder.calls("required"));
+        let not_required = ((self.default.is_some() || self.optional.is_present())
+            && !sets_required)
+            .then(|| quote!(.required(false)));
+
         quote! {
-            <#ty as ::serenity_commands::BasicOption>::create_option(
-                #name,
-                #description,
-            )
+            #option
+            #not_required
             #builder_methods
         }
     }
 
-    fn from_options(selfs: &[Self]) -> (TokenStream, impl Iterator<Item = TokenStream> + '_) {
-        let match_arms = selfs.iter().enumerate().map(|(idx, field)| {
+    fn default_value(&self) -> Option<TokenStream> {
+        self.default.as_ref().map(|default| match &default.0 {
+            Override::Inherit => quote!(::std::default::Default::default()),
+            Override::Explicit(expr) => expr.to_token_stream(),
+        })
+    }
+
+    /// Whether this field's option is required, if it can be determined from
+    /// the field alone.
+    ///
+    /// Flattened fields and fields with `with_create` are unknown, as are
+    /// `required` builder calls with non-literal arguments. Otherwise, fields
+    /// with a `default` or marked `optional` are not required, and the rest
+    /// are decided by [`type_is_required`].
+    fn is_required(&self) -> Option<bool> {
+        if self.flatten.is_present() {
+            return None;
+        }
+
+        if let Some(builder) = &self.builder {
+            if builder.calls("required") {
+                let last_call = builder
+                    .methods
+                    .iter()
+                    .rfind(|call| call.method == "required");
+
+                return match last_call.and_then(|call| call.args.first()) {
+                    Some(Expr::Lit(ExprLit {
+                        lit: Lit::Bool(required),
+                        ..
+                    })) => Some(required.value),
+                    _ => None,
+                };
+            }
+        }
+
+        if self.default.is_some() || self.optional.is_present() {
+            return Some(false);
+        }
+
+        if self.with_create.is_some() {
+            return None;
+        }
+
+        type_is_required(&self.ty)
+    }
+
+    /// Check that no required option comes after an optional one, as Discord
+    /// rejects such commands when they are registered.
+    fn validate_required_order(selfs: &[&Self], acc: &mut Accumulator) {
+        let mut first_optional = None;
+
+        for field in selfs {
+            match (field.is_required(), first_optional) {
+                (Some(false), None) => first_optional = Some(field.ident()),
+                (Some(true), Some(optional)) => acc.push(
+                    Error::custom(format!(
+                        "required option `{}` must come before optional option `{optional}`",
+                        field.ident()
+                    ))
+                    .with_span(field.ident()),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    /// Generate a `Vec` of the options of each field, splicing in the options
+    /// of flattened fields.
+    fn create_options(
+        selfs: &[Self],
+        prefix: Option<&str>,
+        paths: &CratePaths,
+        acc: &mut Accumulator,
+    ) -> TokenStream {
+        let serenity_commands = &paths.serenity_commands;
+        let fields = selfs
+            .iter()
+            .filter(|field| {
+                if field.skip.is_present() {
+                    field.forbid_option_attributes("skip", acc);
+
+                    if field.flatten.is_present() {
+                        acc.push(
+                            Error::custom("`flatten` cannot be used with `skip`")
+                                .with_span(&field.flatten.span()),
+                        );
+                    }
+                }
+
+                !field.skip.is_present()
+            })
+            .collect::<Vec<_>>();
+
+        validate_unique_names(
+            fields
+                .iter()
+                .filter(|field| !field.flatten.is_present())
+                .map(|field| (field.name(), field.ident())),
+            acc,
+        );
+        Self::validate_required_order(&fields, acc);
+
+        if fields.iter().all(|field| !field.flatten.is_present()) {
+            let options = fields
+                .iter()
+                .map(|field| field.create_option(prefix, paths, acc));
+
+            return quote! {
+                ::std::vec![#(#options),*]
+            };
+        }
+
+        let statements = fields.iter().map(|field| {
+            if field.flatten.is_present() {
+                field.forbid_option_attributes("flatten", acc);
+
+                if field.default.is_some() {
+                    acc.push(
+                        Error::custom("`default` cannot be used with `flatten`")
+                            .with_span(field.ident()),
+                    );
+                }
+
+                let ty = &field.ty;
+
+                quote! {
+                    options.extend(
+                        <#ty as #serenity_commands::FlattenOptions>::create_options()
+                    );
+                }
+            } else {
+                let option = field.create_option(prefix, paths, acc);
+
+                quote! {
+                    options.push(#option);
+                }
+            }
+        });
+
+        quote! {
+            {
+                let mut options = ::std::vec::Vec::new();
+                #(#statements)*
+                options
+            }
+        }
+    }
+
+    /// Push an error for each option-only attribute set on a field marked with
+    /// `marker` (`flatten` or `skip`), as such fields do not produce an option
+    /// of their own.
+    fn forbid_option_attributes(&self, marker: &str, acc: &mut Accumulator) {
+        if let Some(name) = &self.name {
+            acc.push(
+                Error::custom(format!("`name` cannot be used with `{marker}`"))
+                    .with_span(&name.span()),
+            );
+        }
+
+        if self.verbatim.is_present() {
+            acc.push(
+                Error::custom(format!("`verbatim` cannot be used with `{marker}`"))
+                    .with_span(&self.verbatim.span()),
+            );
+        }
+
+        if self.builder.is_some() {
+            acc.push(
+                Error::custom(format!("`builder` cannot be used with `{marker}`"))
+                    .with_span(self.ident()),
+            );
+        }
+
+        if self.description_from_name.is_present() {
+            acc.push(
+                Error::custom(format!(
+                    "`description_from_name` cannot be used with `{marker}`"
+                ))
+                .with_span(&self.description_from_name.span()),
+            );
+        }
+
+        if let Some(choices_from) = &self.choices_from {
+            acc.push(
+                Error::custom(format!("`choices_from` cannot be used with `{marker}`"))
+                    .with_span(choices_from),
+            );
+        }
+
+        if let Some(option_type) = &self.option_type {
+            acc.push(
+                Error::custom(format!("`option_type` cannot be used with `{marker}`"))
+                    .with_span(&option_type.span()),
+            );
+        }
+
+        if self.optional.is_present() {
+            acc.push(
+                Error::custom(format!("`optional` cannot be used with `{marker}`"))
+                    .with_span(&self.optional.span()),
+            );
+        }
+
+        for (attribute, path) in [
+            ("with_create", &self.with_create),
+            ("with_parse", &self.with_parse),
+        ] {
+            if let Some(path) = path {
+                acc.push(
+                    Error::custom(format!("`{attribute}` cannot be used with `{marker}`"))
+                        .with_span(path),
+                );
+            }
+        }
+    }
+
+    /// Generate the expression which parses this (basic) field out of slot
+    /// `slot` of the accumulator tuple built by [`Field::from_options`],
+    /// falling back to its default value if the option was not provided.
+    fn parse_slot(&self, slot: &Index, paths: &CratePaths) -> TokenStream {
+        let serenity_commands = &paths.serenity_commands;
+        let ty = &self.ty;
+        let name = self.name();
+
+        let value = self.with_parse.as_ref().map_or_else(
+            || {
+                quote! {
+                    <#ty as #serenity_commands::BasicOption>::from_value_resolved(
+                        #name,
+                        acc.#slot,
+                        resolved,
+                    )
+                }
+            },
+            |with_parse| {
+                quote! {
+                    #with_parse(acc.#slot).map_err(|error| error.with_option_name(#name))
+                }
+            },
+        );
+
+        match self.default_value() {
+            Some(default) => quote! {
+                match acc.#slot {
+                    ::std::option::Option::Some(_) => #value,
+                    ::std::option::Option::None => ::std::result::Result::Ok(#default),
+                }
+            },
+            None => value,
+        }
+    }
+
+    /// Generate the body which extracts each field from `options` and
+    /// constructs `path` out of them, stopping at the first field's error, or
+    /// collecting every field's error into an `Error::Multiple` if
+    /// `collect_errors` is set.
+    ///
+    /// The provided options are only iterated over once: each option's name is
+    /// matched against the (at most 25) field names, and its value is stored
+    /// in the slot of the accumulator tuple belonging to that field. No lookup
+    /// structure is needed, as each field is then read directly out of its
+    /// slot.
+    ///
+    /// Flattened fields are instead extracted from all of `options`, ignoring
+    /// the options which belong to other fields, and skipped fields are set to
+    /// their default value.
+    ///
+    /// Both `options` and `resolved` must be in scope where the body is used.
+    fn from_options(
+        selfs: &[Self],
+        path: &TokenStream,
+        collect_errors: bool,
+        paths: &CratePaths,
+    ) -> TokenStream {
+        if selfs.is_empty() {
+            return quote! {
+                ::std::result::Result::Ok(#path {})
+            };
+        }
+
+        let basic_fields = selfs
+            .iter()
+            .filter(|field| !field.flatten.is_present() && !field.skip.is_present())
+            .collect::<Vec<_>>();
+
+        let match_arms = basic_fields.iter().enumerate().map(|(idx, field)| {
             let idx = Index::from(idx);
             let name = field.name();
 
@@ -474,37 +1031,288 @@ impl Field {
             }
         });
 
-        let inits = iter::repeat(quote!(::std::option::Option::None)).take(selfs.len());
+        let inits = iter::repeat(quote!(::std::option::Option::None)).take(basic_fields.len());
+
+        let fold = (!basic_fields.is_empty()).then(|| {
+            quote! {
+                let acc = ::std::iter::Iterator::fold(
+                    options.iter(),
+                    (#(#inits,)*),
+                    |mut acc, option| {
+                        match option.name.as_str() {
+                            #(#match_arms,)*
+                            _ => {}
+                        }
+
+                        acc
+                    }
+                );
+            }
+        });
 
-        let field_init = selfs.iter().enumerate().map(|(idx, field)| {
-            let ident = field.ident();
+        let serenity_commands = &paths.serenity_commands;
+        let mut idx = 0;
+        let values = selfs.iter().map(|field| {
             let ty = &field.ty;
 
-            let idx = Index::from(idx);
+            if field.skip.is_present() {
+                let default = field
+                    .default_value()
+                    .unwrap_or_else(|| quote!(::std::default::Default::default()));
 
-            quote! {
-                #ident: <#ty as ::serenity_commands::BasicOption>::from_value(
-                    acc.#idx
-                )?
+                return quote! {
+                    ::std::result::Result::<#ty, #serenity_commands::Error>::Ok(#default)
+                };
+            }
+
+            if field.flatten.is_present() {
+                return quote! {
+                    <#ty as #serenity_commands::FlattenOptions>::from_options_resolved(
+                        options,
+                        resolved,
+                    )
+                };
             }
+
+            let slot = Index::from(idx);
+            idx += 1;
+
+            field.parse_slot(&slot, paths)
         });
 
-        let fold = quote! {
-            let acc = ::std::iter::Iterator::fold(
-                options.iter(),
-                (#(#inits,)*),
-                |mut acc, option| {
-                    match option.name.as_str() {
-                        #(#match_arms,)*
-                        _ => {}
-                    }
+        let idents = selfs.iter().map(Self::ident).collect::<Vec<_>>();
+
+        if !collect_errors {
+            return quote! {
+                #fold
+
+                ::std::result::Result::Ok(#path {
+                    #(#idents: #values?),*
+                })
+            };
+        }
+
+        quote! {
+            #fold
 
-                    acc
+            match (#(#values,)*) {
+                (#(::std::result::Result::Ok(#idents),)*) => {
+                    ::std::result::Result::Ok(#path {
+                        #(#idents),*
+                    })
                 }
-            );
+                (#(#idents,)*) => ::std::result::Result::Err(
+                    #serenity_commands::Error::from_errors(
+                        ::std::iter::Iterator::flatten(
+                            ::std::iter::IntoIterator::into_iter([#(#idents.err()),*])
+                        )
+                    )
+                ),
+            }
+        }
+    }
+}
+
+/// Whether an option of type `ty` is required, if it can be determined from
+/// the type's name alone.
+///
+/// `Option` and `Defaulted` are not required, and the built-in `BasicOption`
+/// types are. `Box` and `Arc` are looked through. Any other type is unknown, as
+/// it may be e.g. an alias to `Option` or a choice enum whose `builder` makes
+/// it optional.
+fn type_is_required(ty: &Type) -> Option<bool> {
+    const REQUIRED: &[&str] = &[
+        "bool",
+        "String",
+        "Cow",
+        "BoundedString",
+        "f32",
+        "f64",
+        "u8",
+        "u16",
+        "u32",
+        "u64",
+        "u128",
+        "usize",
+        "i8",
+        "i16",
+        "i32",
+        "i64",
+        "i128",
+        "isize",
+        "AttachmentId",
+        "ChannelId",
+        "GenericId",
+        "RoleId",
+        "UserId",
+        "ResolvedAttachment",
+        "ResolvedUser",
+        "ResolvedMember",
+    ];
+
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+
+    if segment.ident == "Option" || segment.ident == "Defaulted" {
+        return Some(false);
+    }
+
+    if segment.ident == "Box" || segment.ident == "Arc" {
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+
+        return match args.args.first() {
+            Some(GenericArgument::Type(ty)) => type_is_required(ty),
+            _ => None,
         };
+    }
+
+    REQUIRED
+        .iter()
+        .any(|name| segment.ident == name)
+        .then_some(true)
+}
+
+/// The fields of a named struct or variant, which are options rather than
+/// (sub-)commands.
+fn option_fields(fields: &Fields<Field>) -> impl Iterator<Item = &Field> {
+    fields
+        .iter()
+        .filter(move |_| matches!(fields.style, Style::Struct))
+}
+
+/// The [`option_fields`] of each variant.
+fn variant_option_fields<'a>(
+    variants: impl IntoIterator<Item = &'a Variant>,
+) -> impl Iterator<Item = &'a Field> {
+    variants
+        .into_iter()
+        .flat_map(|variant| option_fields(&variant.fields))
+}
+
+/// The bound each field requires of its type: `BasicOption` for fields which
+/// use its implementation, and `FlattenOptions` for flattened fields.
+fn option_bounds<'a>(
+    fields: impl IntoIterator<Item = &'a Field>,
+    paths: &'a CratePaths,
+) -> impl Iterator<Item = (&'a Type, Path)> {
+    let serenity_commands = &paths.serenity_commands;
+
+    fields.into_iter().filter_map(move |field| {
+        if field.flatten.is_present() {
+            Some((&field.ty, parse_quote!(#serenity_commands::FlattenOptions)))
+        } else if field.uses_basic_option() {
+            Some((&field.ty, parse_quote!(#serenity_commands::BasicOption)))
+        } else {
+            None
+        }
+    })
+}
+
+/// Add each of the given bounds to `generics` whose type mentions a type
+/// parameter, so that missing bounds are reported on the type rather than in
+/// generated code.
+///
+/// Bounds are only added once per type, and not at all if the type already
+/// declares a bound on a trait of the same name.
+fn with_bounds<'a>(
+    generics: &Generics,
+    bounds: impl IntoIterator<Item = (&'a Type, Path)>,
+) -> Generics {
+    fn mentions_any(tokens: TokenStream, params: &[&Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => params.contains(&&ident),
+            TokenTree::Group(group) => mentions_any(group.stream(), params),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+        })
+    }
+
+    fn trait_names<'a>(
+        bounds: impl IntoIterator<Item = &'a TypeParamBound>,
+    ) -> impl Iterator<Item = &'a Ident> {
+        bounds.into_iter().filter_map(|bound| match bound {
+            TypeParamBound::Trait(bound) => {
+                bound.path.segments.last().map(|segment| &segment.ident)
+            }
+            _ => None,
+        })
+    }
+
+    let params = generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+    let mut bounded = generics.clone();
+
+    if params.is_empty() {
+        return bounded;
+    }
+
+    let mut declared = generics
+        .type_params()
+        .flat_map(|param| {
+            trait_names(&param.bounds).map(|name| (param.ident.to_string(), name.clone()))
+        })
+        .chain(
+            generics
+                .where_clause
+                .iter()
+                .flat_map(|where_clause| &where_clause.predicates)
+                .filter_map(|predicate| match predicate {
+                    WherePredicate::Type(predicate) => Some(predicate),
+                    _ => None,
+                })
+                .flat_map(|predicate| {
+                    let ty = predicate.bounded_ty.to_token_stream().to_string();
+
+                    trait_names(&predicate.bounds).map(move |name| (ty.clone(), name.clone()))
+                }),
+        )
+        .collect::<HashSet<_>>();
+
+    let where_clause = bounded.make_where_clause();
+
+    for (ty, bound) in bounds {
+        if !mentions_any(ty.to_token_stream(), &params) {
+            continue;
+        }
 
-        (fold, field_init)
+        let name = bound
+            .segments
+            .last()
+            .expect("bounds should be non-empty paths")
+            .ident
+            .clone();
+
+        if declared.insert((ty.to_token_stream().to_string(), name)) {
+            where_clause.predicates.push(parse_quote!(#ty: #bound));
+        }
+    }
+
+    bounded
+}
+
+/// The paths generated code uses to refer to the crates it depends on, as
+/// given with `#[command(serenity = ..., serenity_commands = ...)]`.
+#[derive(Debug, Clone)]
+struct CratePaths {
+    serenity: Path,
+    serenity_commands: Path,
+}
+
+impl CratePaths {
+    fn new(serenity: Option<&Path>, serenity_commands: Option<&Path>) -> Self {
+        Self {
+            serenity: serenity
+                .cloned()
+                .unwrap_or_else(|| parse_quote!(::serenity)),
+            serenity_commands: serenity_commands
+                .cloned()
+                .unwrap_or_else(|| parse_quote!(::serenity_commands)),
+        }
     }
 }
 
@@ -536,6 +1344,13 @@ pub fn derive_sub_command(tokens: proc_macro::TokenStream) -> proc_macro::TokenS
         .into()
 }
 
+#[proc_macro_derive(FlattenOptions, attributes(command))]
+pub fn derive_flatten_options(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    flatten_options::Args::from_derive_input(&parse_macro_input!(tokens))
+        .map_or_else(Error::write_errors, ToTokens::into_token_stream)
+        .into()
+}
+
 #[proc_macro_derive(BasicOption, attributes(choice))]
 pub fn derive_basic_option(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
     basic_option::Args::from_derive_input(&parse_macro_input!(tokens))
@@ -546,6 +1361,7 @@ pub fn derive_basic_option(tokens: proc_macro::TokenStream) -> proc_macro::Token
 fn documentation_string(
     attrs: &[Attribute],
     spanned: &impl Spanned,
+    fallback: Option<LitStr>,
     acc: &mut Accumulator,
 ) -> LitStr {
     let mut doc_comments = attrs
@@ -564,10 +1380,10 @@ fn documentation_string(
         .peekable();
 
     let res = if doc_comments.peek().is_none() {
-        Err(
+        fallback.ok_or_else(|| {
             Error::custom("missing documentation comment (`///`) to use as description")
-                .with_span(spanned),
-        )
+                .with_span(spanned)
+        })
     } else {
         let (span, s) = doc_comments.fold(
             (Span::call_site(), String::new()),
@@ -589,18 +1405,107 @@ fn documentation_string(
         .unwrap_or_else(|| LitStr::new("", Span::call_site()))
 }
 
-fn option_name(ident: &Ident, s: Option<&SpannedValue<String>>) -> LitStr {
+fn option_name(ident: &Ident, s: Option<&SpannedValue<String>>, verbatim: bool) -> LitStr {
     s.map_or_else(
         || {
             let ident_s = ident.to_string();
-            LitStr::new(
-                &ident_s
-                    .strip_prefix("r#")
-                    .unwrap_or(&ident_s)
-                    .to_kebab_case(),
-                ident.span(),
-            )
+            let ident_s = ident_s.strip_prefix("r#").unwrap_or(&ident_s);
+
+            if verbatim {
+                LitStr::new(ident_s, ident.span())
+            } else {
+                LitStr::new(&ident_s.to_kebab_case(), ident.span())
+            }
         },
         |name| LitStr::new(name, name.span()),
     )
 }
+
+fn humanized_name(ident: &Ident) -> LitStr {
+    let ident_s = ident.to_string();
+
+    LitStr::new(
+        &ident_s
+            .strip_prefix("r#")
+            .unwrap_or(&ident_s)
+            .to_title_case(),
+        ident.span(),
+    )
+}
+
+/// Check that `name` is a valid command or option name, i.e. that it matches
+/// `^[-_\p{L}\p{N}]{1,32}$`, is lowercase, and is not only `-` and `_`.
+///
+/// `verbatim` is whether `name` is an identifier used as-is, which is pointed
+/// out if it is not lowercase.
+fn validate_name(name: &LitStr, verbatim: bool, acc: &mut Accumulator) {
+    let value = name.value();
+    let length = value.chars().count();
+
+    if !(1..=32).contains(&length) {
+        acc.push(
+            Error::custom(format!(
+                "invalid name {value:?}: names must be between 1 and 32 characters long"
+            ))
+            .with_span(name),
+        );
+    } else if let Some(c) = value
+        .chars()
+        .find(|&c| !(c == '-' || c == '_' || c.is_alphanumeric()))
+    {
+        acc.push(
+            Error::custom(format!(
+                "invalid name {value:?}: names can only contain letters, numbers, `-` and `_`, \
+                 found {c:?}"
+            ))
+            .with_span(name),
+        );
+    } else if value.chars().any(char::is_uppercase) {
+        let hint = if verbatim {
+            ", and `verbatim` keeps the identifier's casing"
+        } else {
+            ""
+        };
+
+        acc.push(
+            Error::custom(format!(
+                "invalid name {value:?}: names must be lowercase{hint}"
+            ))
+            .with_span(name),
+        );
+    } else if value.chars().all(|c| c == '-' || c == '_') {
+        acc.push(
+            Error::custom(format!(
+                "invalid name {value:?}: names must contain at least one letter or number"
+            ))
+            .with_span(name),
+        );
+    }
+}
+
+/// Check that no two siblings (i.e. the options of a command, or the
+/// sub-commands of a command or group) share a name, as only the first of
+/// them could ever be matched when parsing.
+///
+/// The options of flattened fields cannot be checked, as their names are only
+/// known to the flattened type's own implementation.
+fn validate_unique_names<'a>(
+    names: impl IntoIterator<Item = (LitStr, &'a Ident)>,
+    acc: &mut Accumulator,
+) {
+    let mut seen = Vec::<(String, &Ident)>::new();
+
+    for (name, ident) in names {
+        let value = name.value();
+
+        match seen.iter().find(|(seen_value, _)| *seen_value == value) {
+            Some((_, first)) => acc.push(
+                Error::custom(format!(
+                    "duplicate name {value:?}: used by both `{first}` and `{ident}`"
+                ))
+                .with_span(&name),
+            ),
+            None => seen.push((value, ident)),
+        }
+    }
+}
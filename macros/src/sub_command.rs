@@ -1,14 +1,17 @@
 use darling::{
     ast::{Data, Style},
     error::Accumulator,
-    util::Ignored,
+    util::{Flag, Ignored},
     Error, FromDeriveInput,
 };
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{Generics, Ident};
+use syn::{Generics, Ident, Path};
 
-use crate::{BuilderMethodList, Field};
+use crate::{
+    flatten_options, option_bounds, option_fields, with_bounds, BuilderMethodList, CratePaths,
+    Field,
+};
 
 #[derive(Debug, FromDeriveInput)]
 #[darling(
@@ -21,23 +24,31 @@ pub struct Args {
     data: Data<Ignored, Field>,
 
     builder: Option<BuilderMethodList>,
+    option_description_prefix: Option<String>,
+
+    collect_errors: Flag,
+
+    serenity: Option<Path>,
+    serenity_commands: Option<Path>,
 }
 
 impl Args {
-    fn create_option(&self, acc: &mut Accumulator) -> TokenStream {
+    fn create_option(&self, paths: &CratePaths) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
         let fields = self.data.as_ref().take_struct().unwrap();
 
         let body = match fields.style {
             Style::Struct => {
-                let options = fields.fields.iter().map(|field| field.create_option(acc));
-
                 quote! {
-                    ::serenity::all::CreateCommandOption::new(
-                        ::serenity::all::CommandOptionType::SubCommand,
+                    #serenity::all::CreateCommandOption::new(
+                        #serenity::all::CommandOptionType::SubCommand,
                         name,
                         description
                     )
-                        #(.add_sub_option(#options))*
+                        .set_sub_options(
+                            <Self as #serenity_commands::FlattenOptions>::create_options()
+                        )
                 }
             }
             Style::Tuple => {
@@ -45,13 +56,13 @@ impl Args {
                 let ty = &field.ty;
 
                 quote! {
-                    <#ty as ::serenity_commands::SubCommand>::create_option(name, description)
+                    <#ty as #serenity_commands::SubCommand>::create_option(name, description)
                 }
             }
             Style::Unit => {
                 quote! {
-                    ::serenity::all::CreateCommandOption::new(
-                        ::serenity::all::CommandOptionType::SubCommand,
+                    #serenity::all::CreateCommandOption::new(
+                        #serenity::all::CommandOptionType::SubCommand,
                         name,
                         description,
                     )
@@ -65,7 +76,7 @@ impl Args {
             fn create_option(
                 name: impl ::std::convert::Into<::std::string::String>,
                 description: impl ::std::convert::Into<::std::string::String>,
-            ) -> ::serenity::all::CreateCommandOption {
+            ) -> #serenity::all::CreateCommandOption {
                 #body
                 #builder_methods
             }
@@ -73,30 +84,30 @@ impl Args {
     }
 
     #[allow(clippy::wrong_self_convention)]
-    fn from_value(&self) -> TokenStream {
+    fn from_value(&self, paths: &CratePaths) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
         let Data::Struct(fields) = &self.data else {
             unreachable!()
         };
 
         let body = match fields.style {
             Style::Struct => {
-                let (fold, inits) = Field::from_options(&fields.fields);
-
                 quote! {
-                    let ::serenity::all::CommandDataOptionValue::SubCommand(options) = value else {
+                    let #serenity::all::CommandDataOptionValue::SubCommand(options) = value else {
                         return ::std::result::Result::Err(
-                            ::serenity_commands::Error::IncorrectCommandOptionType {
+                            #serenity_commands::Error::IncorrectCommandOptionType {
+                                name: ::std::option::Option::None,
                                 got: value.kind(),
-                                expected: ::serenity::all::CommandOptionType::SubCommand,
+                                expected: #serenity::all::CommandOptionType::SubCommand,
                             },
                         );
                     };
 
-                    #fold
-
-                    ::std::result::Result::Ok(Self {
-                        #(#inits),*
-                    })
+                    <Self as #serenity_commands::FlattenOptions>::from_options_resolved(
+                        options,
+                        resolved,
+                    )
                 }
             }
             Style::Tuple => {
@@ -104,7 +115,7 @@ impl Args {
                 let ty = &field.ty;
 
                 quote! {
-                    <#ty as ::serenity_commands::SubCommand>::from_value(value)
+                    <#ty as #serenity_commands::SubCommand>::from_value_resolved(value, resolved)
                         .map(Self)
                 }
             }
@@ -117,12 +128,50 @@ impl Args {
 
         quote! {
             fn from_value(
-                value: &::serenity::all::CommandDataOptionValue,
-            ) -> ::serenity_commands::Result<Self> {
+                value: &#serenity::all::CommandDataOptionValue,
+            ) -> #serenity_commands::Result<Self> {
+                <Self as #serenity_commands::SubCommandGroup>::from_value_resolved(
+                    value,
+                    &::std::default::Default::default(),
+                )
+            }
+
+            fn from_value_resolved(
+                value: &#serenity::all::CommandDataOptionValue,
+                resolved: &#serenity::all::CommandDataResolved,
+            ) -> #serenity_commands::Result<Self> {
                 #body
             }
         }
     }
+
+    fn bounded_generics(&self, paths: &CratePaths) -> Generics {
+        let Data::Struct(fields) = &self.data else {
+            unreachable!()
+        };
+
+        with_bounds(&self.generics, option_bounds(option_fields(fields), paths))
+    }
+
+    fn flatten_options(&self, paths: &CratePaths, acc: &mut Accumulator) -> Option<TokenStream> {
+        let Data::Struct(fields) = &self.data else {
+            unreachable!()
+        };
+
+        if matches!(fields.style, Style::Tuple) {
+            return None;
+        }
+
+        Some(flatten_options::implementation(
+            &self.ident,
+            &self.bounded_generics(paths),
+            fields,
+            self.option_description_prefix.as_deref(),
+            self.collect_errors.is_present(),
+            paths,
+            acc,
+        ))
+    }
 }
 
 impl ToTokens for Args {
@@ -130,35 +179,52 @@ impl ToTokens for Args {
         let mut acc = Error::accumulator();
 
         let ident = &self.ident;
+        let paths = CratePaths::new(self.serenity.as_ref(), self.serenity_commands.as_ref());
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
 
-        let create_option = self.create_option(&mut acc);
-        let from_value = self.from_value();
+        let create_option = self.create_option(&paths);
+        let from_value = self.from_value(&paths);
+        let flatten_options = self.flatten_options(&paths, &mut acc);
 
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let generics = self.bounded_generics(&paths);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
         let implementation = quote! {
             #[automatically_derived]
-            impl #impl_generics ::serenity_commands::SubCommandGroup for #ident #ty_generics #where_clause {
+            impl #impl_generics #serenity_commands::SubCommandGroup for #ident #ty_generics #where_clause {
                 #create_option
 
                 #from_value
             }
 
             #[automatically_derived]
-            impl #impl_generics ::serenity_commands::SubCommand for #ident #ty_generics #where_clause {
+            impl #impl_generics #serenity_commands::SubCommand for #ident #ty_generics #where_clause {
                 fn create_option(
                     name: impl ::std::convert::Into<::std::string::String>,
                     description: impl ::std::convert::Into<::std::string::String>,
-                ) -> ::serenity::all::CreateCommandOption {
-                    <Self as ::serenity_commands::SubCommandGroup>::create_option(name, description)
+                ) -> #serenity::all::CreateCommandOption {
+                    <Self as #serenity_commands::SubCommandGroup>::create_option(name, description)
                 }
 
                 fn from_value(
-                    value: &::serenity::all::CommandDataOptionValue,
-                ) -> ::serenity_commands::Result<Self> {
-                    <Self as ::serenity_commands::SubCommandGroup>::from_value(value)
+                    value: &#serenity::all::CommandDataOptionValue,
+                ) -> #serenity_commands::Result<Self> {
+                    <Self as #serenity_commands::SubCommandGroup>::from_value(value)
+                }
+
+                fn from_value_resolved(
+                    value: &#serenity::all::CommandDataOptionValue,
+                    resolved: &#serenity::all::CommandDataResolved,
+                ) -> #serenity_commands::Result<Self> {
+                    <Self as #serenity_commands::SubCommandGroup>::from_value_resolved(
+                        value,
+                        resolved,
+                    )
                 }
             }
+
+            #flatten_options
         };
 
         acc.finish_with(implementation)
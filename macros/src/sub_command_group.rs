@@ -1,9 +1,17 @@
-use darling::{ast::Data, error::Accumulator, util::Ignored, Error, FromDeriveInput};
+use darling::{
+    ast::Data,
+    error::Accumulator,
+    util::{Flag, Ignored},
+    Error, FromDeriveInput,
+};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{Generics, Ident};
+use syn::{parse_quote, Generics, Ident, LitStr, Path};
 
-use crate::{BuilderMethodList, Variant};
+use crate::{
+    option_bounds, validate_unique_names, variant_option_fields, with_bounds, BuilderMethodList,
+    CratePaths, Variant,
+};
 
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(command), supports(enum_named, enum_newtype, enum_unit))]
@@ -13,15 +21,36 @@ pub struct Args {
     data: Data<Variant, Ignored>,
 
     builder: Option<BuilderMethodList>,
+    default_description: Option<LitStr>,
+
+    collect_errors: Flag,
+
+    serenity: Option<Path>,
+    serenity_commands: Option<Path>,
 }
 
 impl Args {
-    fn create_option(&self, acc: &mut Accumulator) -> TokenStream {
+    fn create_option(&self, paths: &CratePaths, acc: &mut Accumulator) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
         let variants = self.data.as_ref().take_enum().unwrap();
 
-        let body = variants
-            .iter()
-            .map(|variant| variant.create_sub_command(acc));
+        validate_unique_names(
+            variants
+                .iter()
+                .map(|variant| (variant.name(), &variant.ident)),
+            acc,
+        );
+
+        let sub_command = parse_quote!(#serenity_commands::SubCommand);
+        let body = variants.iter().map(|variant| {
+            variant.create_sub_command_or_group(
+                &sub_command,
+                self.default_description.as_ref(),
+                paths,
+                acc,
+            )
+        });
 
         let builder_methods = &self.builder;
 
@@ -29,9 +58,9 @@ impl Args {
             fn create_option(
                 name: impl ::std::convert::Into<::std::string::String>,
                 description: impl ::std::convert::Into<::std::string::String>,
-            ) -> ::serenity::all::CreateCommandOption {
-                ::serenity::all::CreateCommandOption::new(
-                    ::serenity::all::CommandOptionType::SubCommandGroup,
+            ) -> #serenity::all::CreateCommandOption {
+                #serenity::all::CreateCommandOption::new(
+                    #serenity::all::CommandOptionType::SubCommandGroup,
                     name,
                     description,
                 )
@@ -42,35 +71,61 @@ impl Args {
     }
 
     #[allow(clippy::wrong_self_convention)]
-    fn from_value(&self) -> TokenStream {
+    fn from_value(&self, paths: &CratePaths) -> TokenStream {
+        let serenity = &paths.serenity;
+        let serenity_commands = &paths.serenity_commands;
         let Data::Enum(variants) = &self.data else {
             unreachable!()
         };
 
-        let arms = variants.iter().map(Variant::from_subcommand_value);
+        let sub_command = parse_quote!(#serenity_commands::SubCommand);
+        let arms = variants.iter().map(|variant| {
+            variant.from_subcommand_or_group_value(
+                &sub_command,
+                self.collect_errors.is_present(),
+                paths,
+            )
+        });
 
         quote! {
             fn from_value(
-                value: &::serenity::all::CommandDataOptionValue,
-            ) -> ::serenity_commands::Result<Self> {
-                let ::serenity::all::CommandDataOptionValue::SubCommandGroup(options) = value else {
-                    return ::std::result::Result::Err(::serenity_commands::Error::IncorrectCommandOptionType {
+                value: &#serenity::all::CommandDataOptionValue,
+            ) -> #serenity_commands::Result<Self> {
+                <Self as #serenity_commands::SubCommandGroup>::from_value_resolved(
+                    value,
+                    &::std::default::Default::default(),
+                )
+            }
+
+            fn from_value_resolved(
+                value: &#serenity::all::CommandDataOptionValue,
+                resolved: &#serenity::all::CommandDataResolved,
+            ) -> #serenity_commands::Result<Self> {
+                let #serenity::all::CommandDataOptionValue::SubCommandGroup(options) = value else {
+                    return ::std::result::Result::Err(#serenity_commands::Error::IncorrectCommandOptionType {
+                        name: ::std::option::Option::None,
                         got: value.kind(),
-                        expected: ::serenity::all::CommandOptionType::SubCommandGroup,
+                        expected: #serenity::all::CommandOptionType::SubCommandGroup,
                     });
                 };
 
-                let [option] = options.as_slice() else {
-                    return ::std::result::Result::Err(::serenity_commands::Error::IncorrectCommandOptionCount {
-                        got: options.len(),
-                        expected: 1,
-                    });
+                let option = match options.as_slice() {
+                    [option] => option,
+                    [] => return ::std::result::Result::Err(
+                        #serenity_commands::Error::MissingSubCommand
+                    ),
+                    _ => return ::std::result::Result::Err(
+                        #serenity_commands::Error::IncorrectCommandOptionCount {
+                            got: options.len(),
+                            expected: 1,
+                        }
+                    ),
                 };
 
                 match option.name.as_str() {
                     #(#arms)*
                     unknown => ::std::result::Result::Err(
-                        ::serenity_commands::Error::UnknownCommandOption(
+                        #serenity_commands::Error::UnknownCommandOption(
                             ::std::borrow::ToOwned::to_owned(unknown)
                         )
                     ),
@@ -85,15 +140,22 @@ impl ToTokens for Args {
         let mut acc = Error::accumulator();
 
         let ident = &self.ident;
+        let paths = CratePaths::new(self.serenity.as_ref(), self.serenity_commands.as_ref());
+        let serenity_commands = &paths.serenity_commands;
 
-        let create_option = self.create_option(&mut acc);
-        let from_value = self.from_value();
+        let create_option = self.create_option(&paths, &mut acc);
+        let from_value = self.from_value(&paths);
 
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let variants = self.data.as_ref().take_enum().unwrap();
+        let generics = with_bounds(
+            &self.generics,
+            option_bounds(variant_option_fields(variants), &paths),
+        );
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
         let implementation = quote! {
             #[automatically_derived]
-            impl #impl_generics ::serenity_commands::SubCommandGroup for #ident #ty_generics #where_clause {
+            impl #impl_generics #serenity_commands::SubCommandGroup for #ident #ty_generics #where_clause {
                 #create_option
 
                 #from_value
@@ -0,0 +1,23 @@
+//! Utilities for responding to autocomplete interactions.
+
+use serenity::all::{AutocompleteChoice, CreateAutocompleteResponse};
+
+/// The maximum number of choices Discord accepts in an autocomplete response.
+pub const MAX_CHOICES: usize = 25;
+
+/// Extension methods for [`CreateAutocompleteResponse`].
+pub trait CreateAutocompleteResponseExt: Sized {
+    /// Set the choices of the response, keeping only the first
+    /// [`MAX_CHOICES`] of them.
+    ///
+    /// Discord rejects responses with more choices than this, so this can be
+    /// used to pass an unbounded iterator of matches directly.
+    #[must_use]
+    fn set_choices_capped(self, choices: impl IntoIterator<Item = AutocompleteChoice>) -> Self;
+}
+
+impl CreateAutocompleteResponseExt for CreateAutocompleteResponse {
+    fn set_choices_capped(self, choices: impl IntoIterator<Item = AutocompleteChoice>) -> Self {
+        self.set_choices(choices.into_iter().take(MAX_CHOICES).collect())
+    }
+}
@@ -1,6 +1,118 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 //! A library for creating/parsing [`serenity`] slash commands.
 //!
+//! # Features
+//!
+//! By default, [`serenity`] is only depended on with its `builder` feature,
+//! which provides the command builders and interaction data types without the
+//! gateway, client or HTTP surface. This keeps the crate usable in lighter
+//! contexts, such as tools which only generate command definitions.
+//!
+//! - `http`: Enables `Commands::register`, `Commands::register_all`,
+//!   `Commands::commands_changed` and `RegisterScope`, which require
+//!   `serenity/model`.
+//! - `testing`: Enables the `testing` module, for building command data in
+//!   tests.
+//!
+//! # Crate Paths
+//!
+//! Code generated by the derives refers to [`serenity`] and this crate as
+//! `::serenity` and `::serenity_commands`. If either is renamed or only
+//! available through a re-export, every derive accepts the paths to use
+//! instead, e.g. `#[command(serenity = my_framework::serenity)]` and
+//! `#[command(serenity_commands = my_framework::commands)]` (or
+//! `#[choice(...)]` for [`BasicOption`]).
+//!
+//! ```rust
+//! mod framework {
+//!     pub use ::serenity;
+//!
+//!     pub mod commands {
+//!         pub use serenity_commands::*;
+//!     }
+//! }
+//!
+//! use framework::commands::{
+//!     BasicOption, Command, Commands, FlattenOptions, SubCommand, SubCommandGroup,
+//! };
+//!
+//! #[derive(BasicOption)]
+//! #[choice(
+//!     option_type = "string",
+//!     serenity = framework::serenity,
+//!     serenity_commands = framework::commands,
+//! )]
+//! enum Color {
+//!     Red,
+//!     Blue,
+//! }
+//!
+//! #[derive(FlattenOptions)]
+//! #[command(
+//!     serenity = framework::serenity,
+//!     serenity_commands = framework::commands,
+//! )]
+//! struct Pagination {
+//!     /// The page to show.
+//!     page: Option<i64>,
+//! }
+//!
+//! #[derive(SubCommand)]
+//! #[command(
+//!     serenity = framework::serenity,
+//!     serenity_commands = framework::commands,
+//! )]
+//! struct Paint {
+//!     /// The color to paint with.
+//!     color: Color,
+//!
+//!     #[command(flatten)]
+//!     pagination: Pagination,
+//! }
+//!
+//! #[derive(SubCommandGroup)]
+//! #[command(
+//!     serenity = framework::serenity,
+//!     serenity_commands = framework::commands,
+//! )]
+//! enum Palette {
+//!     /// Paint with a color.
+//!     Paint(Paint),
+//! }
+//!
+//! #[derive(Command)]
+//! #[command(
+//!     serenity = framework::serenity,
+//!     serenity_commands = framework::commands,
+//! )]
+//! enum Art {
+//!     /// Manage the palette.
+//!     Palette(Palette),
+//! }
+//!
+//! #[derive(Commands)]
+//! #[command(
+//!     serenity = framework::serenity,
+//!     serenity_commands = framework::commands,
+//!     guild_only,
+//!     try_from,
+//! )]
+//! enum AllCommands {
+//!     /// Make some art.
+//!     #[command(integration_types(Guild))]
+//!     Art(Art),
+//!
+//!     /// Rate a color.
+//!     Rate {
+//!         /// The color to rate.
+//!         #[command(option_type = "string")]
+//!         color: Color,
+//!     },
+//! }
+//!
+//! assert_eq!(AllCommands::create_commands().len(), 2);
+//! ```
+//!
 //! # Examples
 //!
 //! ```rust
@@ -107,14 +219,56 @@
 //! }
 //! ```
 
+#[cfg(feature = "http")]
+use std::future::Future;
+use std::{
+    borrow::Cow,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
 use serenity::all::{
-    AttachmentId, ChannelId, CommandData, CommandDataOption, CommandDataOptionValue,
-    CommandOptionType, CreateCommand, CreateCommandOption, GenericId, RoleId, UserId,
+    Attachment, AttachmentId, ChannelId, CommandData, CommandDataOption, CommandDataOptionValue,
+    CommandDataResolved, CommandOptionType, CreateCommand, CreateCommandOption, GenericId,
+    PartialMember, RoleId, User, UserId,
 };
+#[cfg(feature = "http")]
+use serenity::all::{GuildId, Http};
+
+pub mod autocomplete;
+#[cfg(feature = "testing")]
+pub mod testing;
+
 /// Derives [`BasicOption`].
 ///
 /// `option_type` can be `"string"`, `"integer"`, or `"number"`.
 ///
+/// For `"number"` choices, `value` can be either a float or an integer literal.
+/// As floats may lose precision on their way through Discord, the received
+/// value matches a choice if they differ by at most `1e-9` times the larger of
+/// their magnitudes (or by at most `1e-9` for magnitudes below `1`), rather
+/// than only on exact equality. The first matching choice is used.
+///
+/// Each choice's `name` defaults to the `Title Case` variant name. For
+/// `"string"` choices, `value` defaults to the `kebab-case` variant name, or
+/// to the choice's `name` if the enum is marked with
+/// `#[choice(value_from_name)]`. Another case convention can be chosen with
+/// `#[choice(rename_all = "...")]`, which accepts the same conventions as
+/// serde's (`"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`,
+/// `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"` and
+/// `"SCREAMING-KEBAB-CASE"`). `name` and `value` are independent, so a stable
+/// `value` can be pinned with `#[choice(value = ...)]` without changing the
+/// derived `name`, and always takes precedence.
+///
+/// For `"integer"` choices, a variant's literal discriminant (e.g. `Gold = 1`)
+/// is used as its value if `value` is not given.
+///
+/// Variants can be marked with `#[choice(builder(...))]` to call additional
+/// [`CreateCommandOption`] methods right after their choice is added.
+///
+/// The option is required unless the enum's own `#[choice(builder(...))]`
+/// calls `required`, in which case that call takes precedence.
+///
 /// # Examples
 ///
 /// ```rust
@@ -133,9 +287,171 @@ use serenity::all::{
 ///     Bronze,
 /// }
 /// ```
+///
+/// ```rust
+/// use serenity::all::CommandDataOptionValue;
+/// use serenity_commands::BasicOption;
+///
+/// #[derive(Debug, PartialEq, BasicOption)]
+/// #[choice(option_type = "number")]
+/// enum Speed {
+///     #[choice(value = 0.3)]
+///     Slow,
+///
+///     #[choice(value = 1)]
+///     Normal,
+/// }
+///
+/// let speed = |value| Speed::from_value(Some(&CommandDataOptionValue::Number(value)));
+///
+/// assert_eq!(speed(0.3 + 1e-12).unwrap(), Speed::Slow);
+/// assert_eq!(speed(1.0).unwrap(), Speed::Normal);
+/// assert!(speed(0.3001).is_err());
+/// ```
+///
+/// ```rust
+/// use serenity_commands::BasicOption;
+///
+/// #[derive(Debug, BasicOption)]
+/// #[choice(option_type = "string")]
+/// enum Theme {
+///     #[choice(value = "legacy_dark")]
+///     DarkMode,
+///
+///     LightMode,
+/// }
+///
+/// let option = serde_json::to_value(Theme::create_option("theme", "The theme.")).unwrap();
+///
+/// assert_eq!(option["choices"][0]["name"], "Dark Mode");
+/// assert_eq!(option["choices"][0]["value"], "legacy_dark");
+/// assert_eq!(option["choices"][1]["name"], "Light Mode");
+/// assert_eq!(option["choices"][1]["value"], "light-mode");
+/// ```
+///
+/// Each `rename_all` convention applies to the variant name as follows:
+///
+/// ```rust
+/// # use serenity_commands::BasicOption;
+/// #
+/// # macro_rules! values {
+/// #     ($rule:tt) => {{
+/// #         #[derive(BasicOption)]
+/// #         #[choice(option_type = "string", rename_all = $rule)]
+/// #         enum Theme {
+/// #             DarkMode,
+/// #             LightMode,
+/// #         }
+/// #
+/// #         let option = serde_json::to_value(Theme::create_option("theme", "The theme.")).unwrap();
+/// #
+/// #         [option["choices"][0]["value"].clone(), option["choices"][1]["value"].clone()]
+/// #     }};
+/// # }
+/// #
+/// assert_eq!(values!("lowercase"), ["darkmode", "lightmode"]);
+/// assert_eq!(values!("UPPERCASE"), ["DARKMODE", "LIGHTMODE"]);
+/// assert_eq!(values!("PascalCase"), ["DarkMode", "LightMode"]);
+/// assert_eq!(values!("camelCase"), ["darkMode", "lightMode"]);
+/// assert_eq!(values!("snake_case"), ["dark_mode", "light_mode"]);
+/// assert_eq!(values!("SCREAMING_SNAKE_CASE"), ["DARK_MODE", "LIGHT_MODE"]);
+/// assert_eq!(values!("kebab-case"), ["dark-mode", "light-mode"]);
+/// assert_eq!(values!("SCREAMING-KEBAB-CASE"), ["DARK-MODE", "LIGHT-MODE"]);
+/// ```
+///
+/// Discord has no boolean choices, so toggles presented as named choices (e.g.
+/// "On"/"Off") are modelled as a two-choice enum which converts into a
+/// [`bool`]:
+///
+/// ```rust
+/// use serenity_commands::{BasicOption, Command};
+///
+/// #[derive(Debug, Clone, Copy, BasicOption)]
+/// #[choice(option_type = "string")]
+/// enum Toggle {
+///     On,
+///     Off,
+/// }
+///
+/// impl From<Toggle> for bool {
+///     fn from(toggle: Toggle) -> Self {
+///         matches!(toggle, Toggle::On)
+///     }
+/// }
+///
+/// #[derive(Command)]
+/// struct Notifications {
+///     /// Whether to send notifications.
+///     state: Toggle,
+/// }
+///
+/// # let command = Notifications { state: Toggle::On };
+/// let enabled = bool::from(command.state);
+/// # assert!(enabled);
+/// ```
 pub use serenity_commands_macros::BasicOption;
 /// Derives [`Command`].
 ///
+/// Names are derived from identifiers by converting them to `kebab-case`,
+/// unless `#[command(name = "...")]` is given. Fields and variants marked with
+/// `#[command(verbatim)]` use their identifier as-is instead, e.g. to keep
+/// `user_id` rather than `user-id`. As Discord only accepts lowercase names,
+/// the identifier must still be lowercase: `verbatim` does not bypass name
+/// validation, so a name such as `myCommand` has to be lowercased or given
+/// with `name` instead.
+///
+/// Every option needs a documentation comment to use as its description, unless
+/// it is marked with `#[command(description_from_name)]`, in which case its
+/// humanized name (e.g. `"Message"` for `message`) is used instead.
+///
+/// Structs and named variants can be marked with
+/// `#[command(option_description_prefix = "...")]` to prepend a shared prefix
+/// to the description of each of their options.
+///
+/// Options received from Discord which do not correspond to any field (e.g.
+/// after a field has been removed, but before the command is re-registered)
+/// are ignored rather than causing an error.
+///
+/// Options can be marked with `#[command(default = ...)]` to make them
+/// non-required, using the given expression when they are not provided. A
+/// bare `#[command(default)]` uses the option type's [`Default`]
+/// implementation.
+///
+/// Options can also be marked with `#[command(optional)]` to make them
+/// non-required without changing the field's type or giving a default. The
+/// option's type is then extracted as usual, so e.g. a plain `String` field
+/// fails with [`Error::MissingRequiredCommandOption`] when the option is not
+/// provided, while a type which accepts missing values can handle it itself.
+///
+/// Discord rejects commands whose required options come after optional ones,
+/// so this order is checked at compile time where possible. Options are
+/// considered optional if they are an [`Option`] or [`Defaulted`], have a
+/// `default`, are marked `optional`, or call `required(false)` in their
+/// `builder`, and required if they are one of this crate's own
+/// [`BasicOption`] types (or call `required(true)`). As other types, such as
+/// aliases or choice enums, may be either, they are not checked.
+///
+/// The [`BasicOption`] implementation of an option's type can be replaced for
+/// either direction independently: `#[command(with_create = path)]` calls
+/// `path(name, description)` to create the option, and
+/// `#[command(with_parse = path)]` calls `path(value)` to extract it, with the
+/// same signatures as [`BasicOption::create_option`] and
+/// [`BasicOption::from_value`].
+///
+/// The type of an option can be overridden with
+/// `#[command(option_type = "...")]` (one of `"string"`, `"integer"`,
+/// `"boolean"`, `"user"`, `"channel"`, `"role"`, `"mentionable"`, `"number"` or
+/// `"attachment"`), while still extracting it with the field type's
+/// [`BasicOption::from_value`] (or `with_parse`). It is up to the field type
+/// to accept the values Discord sends for the overridden type: e.g. a
+/// [`String`] field overridden to `"integer"` always fails to parse, as
+/// Discord then sends an integer.
+///
+/// Fields marked with `#[command(skip)]` are not options at all, and are
+/// initialized with [`Default::default`] (or the expression given with
+/// `#[command(skip, default = ...)]`) instead. This allows commands to carry
+/// state which is not provided by the user.
+///
 /// # Examples
 ///
 /// ## Struct
@@ -155,6 +471,46 @@ pub use serenity_commands_macros::BasicOption;
 /// }
 /// ```
 ///
+/// ## Newtype Struct
+///
+/// The inner type must implement [`Command`].
+///
+/// ```rust
+/// use serenity_commands::Command;
+///
+/// #[derive(Command)]
+/// struct Add {
+///     /// First number.
+///     a: f64,
+///
+///     /// Second number.
+///     b: f64,
+/// }
+///
+/// #[derive(Command)]
+/// struct Sum(Add);
+/// ```
+///
+/// ## Default and Skipped Fields
+///
+/// ```rust
+/// use serenity_commands::Command;
+///
+/// #[derive(Command)]
+/// struct List {
+///     /// The page to show.
+///     #[command(default = 1)]
+///     page: i64,
+///
+///     /// Whether to show hidden items.
+///     #[command(default)]
+///     show_hidden: bool,
+///
+///     #[command(skip)]
+///     cached_total: Option<usize>,
+/// }
+/// ```
+///
 /// ## Enum
 ///
 /// Each field of named variants must implement [`BasicOption`].
@@ -163,6 +519,9 @@ pub use serenity_commands_macros::BasicOption;
 /// by extension, [`SubCommand`], as [`SubCommand`] is a sub-trait of
 /// [`SubCommandGroup`]).
 ///
+/// Variants without a documentation comment use the description given with
+/// `#[command(default_description = "...")]` on the enum, if any.
+///
 /// ```rust
 /// use serenity_commands::{Command, SubCommandGroup};
 ///
@@ -188,6 +547,81 @@ pub use serenity_commands_macros::BasicOption;
 ///     /// Moderation utilities.
 ///     Mod(ModUtilities),
 /// }
+/// ```
+///
+/// ## Generic Struct
+///
+/// A [`BasicOption`] bound is added automatically for the type of each option
+/// which mentions a type parameter.
+///
+/// ```rust
+/// use serenity_commands::Command;
+///
+/// #[derive(Command)]
+/// struct Add<T> {
+///     /// First number.
+///     a: T,
+///
+///     /// Second number.
+///     b: T,
+/// }
+/// ```
+///
+/// ## Lifetimes
+///
+/// Lifetime parameters are carried through to the generated implementations.
+/// As options are always extracted into owned values, borrowed fields must
+/// either be skipped (and constructible with any lifetime, e.g. `&str`'s
+/// [`Default`]) or be a type such as [`Cow`], which is always
+/// [`Cow::Owned`] when extracted.
+///
+/// ```rust
+/// use std::borrow::Cow;
+///
+/// use serenity_commands::Command;
+///
+/// #[derive(Command)]
+/// struct Echo<'a> {
+///     /// The message to echo.
+///     message: Cow<'a, str>,
+///
+///     #[command(skip)]
+///     prefix: &'a str,
+/// }
+/// ```
+///
+/// ## Flattened Options
+///
+/// Fields marked with `#[command(flatten)]` must implement [`FlattenOptions`]
+/// rather than [`BasicOption`]. Their options are inlined into the containing
+/// command or sub-command instead of being nested.
+///
+/// Sibling options and sub-commands with the same name are rejected at compile
+/// time. Options inlined by flattening are not included in this check, as
+/// their names are only known to the flattened type, so a clash between them
+/// is only reported by Discord when the command is registered.
+///
+/// ```rust
+/// use serenity_commands::{Command, SubCommand};
+///
+/// #[derive(SubCommand)]
+/// struct Pagination {
+///     /// The page to show.
+///     page: Option<i64>,
+///
+///     /// The number of items per page.
+///     per_page: Option<i64>,
+/// }
+///
+/// #[derive(Command)]
+/// struct List {
+///     /// The category to list.
+///     category: String,
+///
+///     #[command(flatten)]
+///     pagination: Pagination,
+/// }
+/// ```
 pub use serenity_commands_macros::Command;
 /// Derives [`Commands`].
 ///
@@ -197,6 +631,32 @@ pub use serenity_commands_macros::Command;
 ///
 /// The inner type of newtype variants must implement [`Command`].
 ///
+/// By default, extracting a command stops at the first option which fails to
+/// parse. The whole type can be marked with `#[command(collect_errors)]` to
+/// parse every option of its commands and combine their errors into an
+/// [`Error::Multiple`], which [`Commands::from_command_data_verbose`] splits
+/// back up. Nested [`Command`], [`SubCommandGroup`], [`SubCommand`] and
+/// [`FlattenOptions`] types accept the same attribute for their own options.
+///
+/// Variants can be marked with `#[command(nsfw)]` to mark the command as
+/// age-restricted, and with `#[command(integration_types(Guild, User))]` to
+/// control where the command can be installed.
+///
+/// The whole type can be marked with `#[command(guild_only)]` to restrict
+/// every command to guilds. This is applied before each command's `builder`,
+/// so individual commands can still override it with
+/// `#[command(builder(contexts(...)))]`.
+///
+/// The whole type can be marked with `#[command(prefix = "...")]` to prepend
+/// a prefix (e.g. `"acme-"`) to the name of every command which does not have
+/// an explicit `#[command(name = "...")]`. The prefix is prepended after the
+/// name is derived from the identifier, so `verbatim` commands keep their
+/// identifier as-is after the prefix.
+///
+/// The whole type can also be marked with `#[command(try_from)]` to implement
+/// [`TryFrom<&CommandData>`](TryFrom) by delegating to
+/// [`Commands::from_command_data`].
+///
 /// ```rust
 /// use serenity_commands::{Command, Commands};
 ///
@@ -219,11 +679,157 @@ pub use serenity_commands_macros::Command;
 ///     /// Do math operations.
 ///     Math(MathCommand),
 /// }
+/// ```
+///
+/// Structs are treated as a single top-level command, named after the struct
+/// unless `#[command(name = "...")]` is given. Attributes which would otherwise
+/// be placed on a variant are placed on the struct itself. The command's name
+/// is also available as an inherent `NAME` constant, without building the
+/// command.
+///
+/// ```rust
+/// use serenity_commands::Commands;
+///
+/// /// Echo a message.
+/// #[derive(Commands)]
+/// struct Echo {
+///     /// The message to echo.
+///     message: String,
+/// }
+///
+/// assert_eq!(Echo::create_commands().len(), 1);
+/// assert_eq!(Echo::NAME, "echo");
+/// ```
+///
+/// Enums can be marked with `#[command(dispatch)]` to generate a
+/// `<Enum>Run` trait, with a method (named after the variant in `snake_case`)
+/// for each command taking its data, along with a `run` method dispatching to
+/// the matching method of a handler implementing it.
+///
+/// ```rust
+/// use serenity_commands::Commands;
+///
+/// #[derive(Commands)]
+/// #[command(dispatch)]
+/// enum AllCommands {
+///     /// Ping the bot.
+///     Ping,
+///
+///     /// Echo a message.
+///     Echo {
+///         /// The message to echo.
+///         message: String,
+///     },
+/// }
+///
+/// struct Handler;
+///
+/// impl AllCommandsRun for Handler {
+///     type Output = String;
+///
+///     async fn ping(&self) -> String {
+///         "Pong!".to_owned()
+///     }
+///
+///     async fn echo(&self, message: String) -> String {
+///         message
+///     }
+/// }
+///
+/// # async fn run(command: AllCommands) {
+/// let response = command.run(&Handler).await;
+/// # }
+/// ```
+///
+/// A newtype variant containing [`CommandData`] can be marked with
+/// `#[command(other)]` to capture any command which does not match another
+/// variant, instead of returning [`Error::UnknownCommand`]. It is not
+/// included in [`Commands::create_commands`] or [`Commands::COMMAND_NAMES`],
+/// and has no name of its own to match, so attributes which only apply to
+/// created commands (such as `name` or `builder`) are rejected on it. With
+/// `#[command(dispatch)]`, its handler receives the captured [`CommandData`].
+///
+/// ```rust
+/// use serenity::all::CommandData;
+/// use serenity_commands::Commands;
+///
+/// #[derive(Commands)]
+/// enum AllCommands {
+///     /// Ping the bot.
+///     Ping,
+///
+///     #[command(other)]
+///     Unknown(CommandData),
+/// }
+///
+/// assert_eq!(AllCommands::create_commands().len(), 1);
+/// ```
+///
+/// Variants gated behind `#[cfg(...)]` are excluded from both
+/// [`Commands::create_commands`] and [`Commands::from_command_data`] when
+/// disabled, so a command is never registered without being parseable (or
+/// vice versa).
+///
+/// ```rust
+/// use serenity_commands::Commands;
+///
+/// #[derive(Commands)]
+/// enum AllCommands {
+///     /// Ping the bot.
+///     Ping,
+///
+///     /// Always enabled.
+///     #[cfg(all())]
+///     Enabled,
+///
+///     /// Never enabled.
+///     #[cfg(any())]
+///     Disabled,
+/// }
+///
+/// assert_eq!(AllCommands::create_commands().len(), 2);
+/// ```
 pub use serenity_commands_macros::Commands;
+/// Derives [`FlattenOptions`].
+///
+/// Each field must implement [`BasicOption`], and the same field attributes as
+/// for [`Command`] are accepted.
+///
+/// This is only needed for sets of options which are not sub-commands
+/// themselves, as [`SubCommand`]'s derive macro already implements
+/// [`FlattenOptions`] for named and unit structs.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_commands::{Command, FlattenOptions};
+///
+/// #[derive(FlattenOptions)]
+/// #[command(option_description_prefix = "Pagination:")]
+/// pub struct Pagination {
+///     /// The page to show.
+///     #[command(default = 1)]
+///     pub page: i64,
+///
+///     /// The number of items per page.
+///     #[command(default = 10)]
+///     pub per_page: i64,
+/// }
+///
+/// #[derive(Command)]
+/// struct List {
+///     #[command(flatten)]
+///     pagination: Pagination,
+/// }
+/// ```
+pub use serenity_commands_macros::FlattenOptions;
 /// Derives [`SubCommand`].
 ///
 /// Each field must implement [`BasicOption`].
 ///
+/// Named and unit structs also implement [`FlattenOptions`], so that their
+/// options can be flattened into other commands with `#[command(flatten)]`.
+///
 /// # Examples
 ///
 /// ```rust
@@ -245,6 +851,9 @@ pub use serenity_commands_macros::SubCommand;
 ///
 /// The inner type of newtype variants must implement [`SubCommand`].
 ///
+/// Variants without a documentation comment use the description given with
+/// `#[command(default_description = "...")]` on the enum, if any.
+///
 /// # Examples
 ///
 /// ```rust
@@ -270,6 +879,10 @@ pub use serenity_commands_macros::SubCommand;
 ///         a: f64,
 ///     },
 /// }
+/// ```
+///
+/// Sub-command groups cannot contain other sub-command groups, as Discord only
+/// allows two levels of nesting below a command.
 pub use serenity_commands_macros::SubCommandGroup;
 use thiserror::Error;
 
@@ -279,16 +892,46 @@ use thiserror::Error;
 /// [`Error`]: enum@Error
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The kind of entity a mentionable command option refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MentionableKind {
+    /// A user.
+    User,
+
+    /// A role.
+    Role,
+}
+
+impl MentionableKind {
+    /// Determine what `id` refers to by looking it up in `resolved`.
+    #[must_use]
+    pub fn resolve(id: GenericId, resolved: &CommandDataResolved) -> Option<Self> {
+        if resolved.users.contains_key(&UserId::new(id.get())) {
+            Some(Self::User)
+        } else if resolved.roles.contains_key(&RoleId::new(id.get())) {
+            Some(Self::Role)
+        } else {
+            None
+        }
+    }
+}
+
 /// An error which can occur when extracting data from a command interaction.
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum Error {
     /// An unknown command was provided.
     #[error("unknown command: {0}")]
     UnknownCommand(String),
 
     /// An incorrect command option type was provided.
-    #[error("incorrect command option type: got {got:?}, expected {expected:?}")]
+    #[error(
+        "incorrect command option type{}: got {got:?}, expected {expected:?}",
+        .name.as_ref().map(|name| format!(" for `{name}`")).unwrap_or_default()
+    )]
     IncorrectCommandOptionType {
+        /// The name of the command option, if known.
+        name: Option<String>,
+
         /// The type of command option that was provided.
         got: CommandOptionType,
 
@@ -296,6 +939,23 @@ pub enum Error {
         expected: CommandOptionType,
     },
 
+    /// A mentionable was provided where another command option type was
+    /// expected, and [`CommandData::resolved`] shows what was mentioned.
+    #[error(
+        "incorrect command option type{}: got a mentioned {got:?}, expected {expected:?}",
+        .name.as_ref().map(|name| format!(" for `{name}`")).unwrap_or_default()
+    )]
+    IncorrectMentionableKind {
+        /// The name of the command option, if known.
+        name: Option<String>,
+
+        /// The kind of entity which was mentioned.
+        got: MentionableKind,
+
+        /// The type of command option that was expected.
+        expected: CommandOptionType,
+    },
+
     /// An incorrect number of command options were provided.
     #[error("incorrect command option count: got {got}, expected {expected}")]
     IncorrectCommandOptionCount {
@@ -306,6 +966,10 @@ pub enum Error {
         expected: usize,
     },
 
+    /// A sub-command was expected, but no command options were provided.
+    #[error("expected a sub-command, but no command options were provided")]
+    MissingSubCommand,
+
     /// An unknown command option was provided.
     #[error("unknown command option: {0}")]
     UnknownCommandOption(String),
@@ -314,30 +978,402 @@ pub enum Error {
     #[error("required command option not provided")]
     MissingRequiredCommandOption,
 
+    /// An autocomplete value was provided where a complete value was expected.
+    #[error("unexpected autocomplete option")]
+    UnexpectedAutocompleteOption,
+
     /// An unknown choice was provided.
     #[error("unknown choice: {0}")]
     UnknownChoice(String),
 
     /// An error occurred within a custom implementation.
     #[error(transparent)]
-    Custom(#[from] Box<dyn std::error::Error + Send + Sync>),
-}
+    Custom(Arc<dyn std::error::Error + Send + Sync>),
 
-/// A utility for creating commands and extracting their data from application
-/// commands.
-pub trait Commands: Sized {
-    /// List of top-level commands.
-    fn create_commands() -> Vec<CreateCommand>;
+    /// A custom implementation failed to parse the value of a command option.
+    #[error("invalid value {input:?} for `{name}`: {source}")]
+    InvalidValue {
+        /// The name of the command option.
+        name: String,
 
-    /// Extract data from [`CommandData`].
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the implementation fails.
-    fn from_command_data(data: &CommandData) -> Result<Self>;
-}
+        /// The raw value which failed to parse.
+        input: String,
 
-/// A top-level command for use with [`Commands`].
+        /// The underlying error.
+        source: Arc<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// A command option referenced an entity which was not present in
+    /// [`CommandData::resolved`].
+    #[error("command option referenced an unresolved entity")]
+    MissingResolvedValue,
+
+    /// Multiple errors occurred.
+    ///
+    /// This is only returned by types marked with `#[command(collect_errors)]`.
+    #[error("multiple errors occurred: {}", join_errors(.0))]
+    Multiple(Vec<Self>),
+}
+
+fn join_errors(errors: &[Error]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl Error {
+    /// Create an [`Error::Custom`] from any error or message.
+    #[must_use]
+    pub fn custom(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Custom(Arc::from(error.into()))
+    }
+
+    /// Create an [`Error::InvalidValue`] for the command option named `name`,
+    /// which failed to parse `input`.
+    #[must_use]
+    pub fn custom_for(
+        name: impl Into<String>,
+        input: impl Into<String>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self::InvalidValue {
+            name: name.into(),
+            input: input.into(),
+            source: Arc::from(source.into()),
+        }
+    }
+
+    /// Get a reference to the error inside of an [`Error::Custom`] or
+    /// [`Error::InvalidValue`], if it is of type `E`.
+    ///
+    /// This allows recovering the concrete error type returned by a custom
+    /// [`BasicOption`] implementation through [`Error::custom`] or
+    /// [`Error::custom_for`].
+    #[must_use]
+    pub fn downcast_custom_ref<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        match self {
+            Self::Custom(error) | Self::InvalidValue { source: error, .. } => error.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    /// Combine multiple errors into one.
+    ///
+    /// If there is only a single error, it is returned as-is. Otherwise, the
+    /// errors are combined into an [`Error::Multiple`].
+    #[must_use]
+    pub fn from_errors(errors: impl IntoIterator<Item = Self>) -> Self {
+        let mut errors = errors
+            .into_iter()
+            .flat_map(Self::into_errors)
+            .collect::<Vec<_>>();
+
+        if errors.len() == 1 {
+            errors.remove(0)
+        } else {
+            Self::Multiple(errors)
+        }
+    }
+
+    /// Split this error into the errors it consists of.
+    ///
+    /// This is the inverse of [`Error::from_errors`].
+    #[must_use]
+    pub fn into_errors(self) -> Vec<Self> {
+        match self {
+            Self::Multiple(errors) => errors,
+            error => vec![error],
+        }
+    }
+
+    /// Attach the name of the command option this error occurred in, if the
+    /// error refers to a command option and its name is not already known.
+    #[must_use]
+    pub fn with_option_name(self, option_name: &str) -> Self {
+        match self {
+            Self::IncorrectCommandOptionType {
+                name: None,
+                got,
+                expected,
+            } => Self::IncorrectCommandOptionType {
+                name: Some(option_name.to_owned()),
+                got,
+                expected,
+            },
+            Self::IncorrectMentionableKind {
+                name: None,
+                got,
+                expected,
+            } => Self::IncorrectMentionableKind {
+                name: Some(option_name.to_owned()),
+                got,
+                expected,
+            },
+            error => error,
+        }
+    }
+
+    /// Turn an [`Error::IncorrectCommandOptionType`] caused by the mentionable
+    /// `value` into an [`Error::IncorrectMentionableKind`], if `resolved`
+    /// shows what was mentioned.
+    fn with_mentionable_kind(
+        self,
+        value: Option<&CommandDataOptionValue>,
+        resolved: &CommandDataResolved,
+    ) -> Self {
+        match (self, value) {
+            (
+                Self::IncorrectCommandOptionType {
+                    name,
+                    got: CommandOptionType::Mentionable,
+                    expected,
+                },
+                Some(CommandDataOptionValue::Mentionable(id)),
+            ) => match MentionableKind::resolve(*id, resolved) {
+                Some(got) => Self::IncorrectMentionableKind {
+                    name,
+                    got,
+                    expected,
+                },
+                None => Self::IncorrectCommandOptionType {
+                    name,
+                    got: CommandOptionType::Mentionable,
+                    expected,
+                },
+            },
+            (error, _) => error,
+        }
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for Error {
+    fn from(error: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        Self::Custom(Arc::from(error))
+    }
+}
+
+/// A utility for creating commands and extracting their data from application
+/// commands.
+pub trait Commands: Sized {
+    /// The names of the top-level commands, in the order they are created.
+    ///
+    /// This is used by [`Commands::create_commands_filtered`] to select
+    /// commands without building them first, and can be used to build e.g. a
+    /// help command. The derive lists every command's name. By default, this
+    /// is empty, so no commands are ever selected by name.
+    const COMMAND_NAMES: &'static [&'static str] = &[];
+
+    /// List of top-level commands.
+    ///
+    /// By default, this collects [`Commands::create_commands_iter`].
+    /// Implementations must override at least one of the two.
+    #[must_use]
+    fn create_commands() -> Vec<CreateCommand> {
+        Self::create_commands_iter().collect()
+    }
+
+    /// Iterator over the top-level commands.
+    ///
+    /// This is useful for merging multiple sets of commands together without
+    /// allocating an intermediate [`Vec`] for each.
+    ///
+    /// By default, this iterates over [`Commands::create_commands`]. The
+    /// derive overrides this to yield the commands directly.
+    #[must_use]
+    fn create_commands_iter() -> impl Iterator<Item = CreateCommand> {
+        Self::create_commands().into_iter()
+    }
+
+    /// List of the top-level commands whose names satisfy `predicate`.
+    ///
+    /// This allows varying the set of registered commands at runtime, e.g.
+    /// only registering some commands in certain guilds.
+    ///
+    /// Commands are matched with their names in [`Commands::COMMAND_NAMES`] by
+    /// position, so commands without a listed name are never selected.
+    fn create_commands_filtered(predicate: impl Fn(&str) -> bool) -> Vec<CreateCommand> {
+        Self::create_commands_iter()
+            .zip(Self::COMMAND_NAMES)
+            .filter(|(_, name)| predicate(name))
+            .map(|(command, _)| command)
+            .collect()
+    }
+
+    /// Extract data from [`CommandData`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the implementation fails.
+    fn from_command_data(data: &CommandData) -> Result<Self>;
+
+    /// Extract data from [`CommandData`], returning every error which occurred
+    /// rather than a single one.
+    ///
+    /// Unless the types being extracted are marked with
+    /// `#[command(collect_errors)]`, this is at most the first error.
+    ///
+    /// # Errors
+    ///
+    /// Returns all errors encountered if the implementation fails.
+    fn from_command_data_verbose(data: &CommandData) -> std::result::Result<Self, Vec<Error>> {
+        Self::from_command_data(data).map_err(Error::into_errors)
+    }
+
+    /// Register the commands in [`Commands::create_commands`] within the given
+    /// scope, overwriting any existing commands in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the commands fail to be registered.
+    #[cfg(feature = "http")]
+    fn register(
+        http: &Http,
+        scope: RegisterScope,
+    ) -> impl Future<Output = serenity::Result<Vec<serenity::all::Command>>> + Send {
+        async move { scope.set_commands(http, Self::create_commands()).await }
+    }
+
+    /// Register each command in [`Commands::create_commands`] individually,
+    /// within the given scope.
+    ///
+    /// Commands are created one at a time, so a failure to register one
+    /// command does not prevent the others from being registered. The returned
+    /// results are in the same order as [`Commands::create_commands`].
+    #[cfg(feature = "http")]
+    fn register_all(
+        http: &Http,
+        scope: RegisterScope,
+    ) -> impl Future<Output = Vec<serenity::Result<serenity::all::Command>>> + Send {
+        async move {
+            let mut results = Vec::new();
+
+            for command in Self::create_commands() {
+                results.push(scope.create_command(http, command).await);
+            }
+
+            results
+        }
+    }
+
+    /// Whether the commands in [`Commands::create_commands`] differ from the
+    /// `existing` commands reported by Discord.
+    ///
+    /// Commands are compared structurally by name, description, options and
+    /// any other fields set when creating them. Fields which Discord fills in
+    /// on its own (such as IDs and versions) are ignored. This can be used to
+    /// skip redundant registration when nothing has changed.
+    #[cfg(feature = "http")]
+    #[must_use]
+    fn commands_changed(existing: &[serenity::all::Command]) -> bool {
+        let commands = Self::create_commands();
+
+        commands.len() != existing.len()
+            || commands.iter().any(|command| {
+                let Ok(mut command) = serde_json::to_value(command) else {
+                    return true;
+                };
+
+                // Unset permissions are omitted when creating a command, but
+                // must still be compared against those reported by Discord.
+                if let Some(command) = command.as_object_mut() {
+                    command
+                        .entry("default_member_permissions")
+                        .or_insert(serde_json::Value::Null);
+                }
+
+                !existing.iter().any(|existing| {
+                    serde_json::to_value(existing)
+                        .is_ok_and(|existing| json_is_subset(&command, &existing))
+                })
+            })
+    }
+}
+
+/// Whether every field set in `created` has an equal value in `existing`.
+///
+/// Empty values are considered equal to missing ones, and numbers are compared
+/// by value regardless of whether they are integers or floats.
+#[cfg(feature = "http")]
+fn json_is_subset(created: &serde_json::Value, existing: &serde_json::Value) -> bool {
+    use serde_json::Value;
+
+    fn is_empty(value: Option<&Value>) -> bool {
+        match value {
+            None | Some(Value::Null) => true,
+            Some(Value::Array(array)) => array.is_empty(),
+            Some(Value::Object(object)) => object.is_empty(),
+            Some(_) => false,
+        }
+    }
+
+    match (created, existing) {
+        (Value::Object(created), Value::Object(existing)) => {
+            created.iter().all(|(key, created)| {
+                if is_empty(Some(created)) {
+                    is_empty(existing.get(key))
+                } else {
+                    existing
+                        .get(key)
+                        .is_some_and(|existing| json_is_subset(created, existing))
+                }
+            })
+        }
+        (Value::Array(created), Value::Array(existing)) => {
+            created.len() == existing.len()
+                && created
+                    .iter()
+                    .zip(existing)
+                    .all(|(created, existing)| json_is_subset(created, existing))
+        }
+        (Value::Number(created), Value::Number(existing)) => created.as_f64() == existing.as_f64(),
+        _ => (is_empty(Some(created)) && is_empty(Some(existing))) || created == existing,
+    }
+}
+
+/// The scope in which to register commands.
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegisterScope {
+    /// Register commands globally.
+    Global,
+
+    /// Register commands in a single guild.
+    Guild(GuildId),
+}
+
+#[cfg(feature = "http")]
+impl RegisterScope {
+    async fn set_commands(
+        self,
+        http: &Http,
+        commands: Vec<CreateCommand>,
+    ) -> serenity::Result<Vec<serenity::all::Command>> {
+        match self {
+            Self::Global => serenity::all::Command::set_global_commands(http, commands).await,
+            Self::Guild(guild_id) => guild_id.set_commands(http, commands).await,
+        }
+    }
+
+    async fn create_command(
+        self,
+        http: &Http,
+        command: CreateCommand,
+    ) -> serenity::Result<serenity::all::Command> {
+        match self {
+            Self::Global => serenity::all::Command::create_global_command(http, command).await,
+            Self::Guild(guild_id) => guild_id.create_command(http, command).await,
+        }
+    }
+}
+
+/// A top-level command for use with [`Commands`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a command",
+    label = "`{Self}` does not implement `Command`",
+    note = "add `#[derive(Command)]` to `{Self}`"
+)]
 pub trait Command: Sized {
     /// Create the command.
     fn create_command(name: impl Into<String>, description: impl Into<String>) -> CreateCommand;
@@ -348,10 +1384,33 @@ pub trait Command: Sized {
     ///
     /// Returns an error if the implementation fails.
     fn from_options(options: &[CommandDataOption]) -> Result<Self>;
+
+    /// Extract data from a list of [`CommandDataOption`]s, looking up
+    /// referenced entities (e.g. attachments) in `resolved`.
+    ///
+    /// By default, this delegates to [`Command::from_options`]. The derives
+    /// override this, and [`Commands::from_command_data`] calls it with
+    /// [`CommandData::resolved`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the implementation fails.
+    fn from_options_resolved(
+        options: &[CommandDataOption],
+        resolved: &CommandDataResolved,
+    ) -> Result<Self> {
+        let _ = resolved;
+        Self::from_options(options)
+    }
 }
 
 /// A sub-command group which can be nested inside of a [`Command`] and contains
 /// [`SubCommand`]s.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a sub-command group",
+    label = "`{Self}` does not implement `SubCommandGroup`",
+    note = "add `#[derive(SubCommandGroup)]` or `#[derive(SubCommand)]` to `{Self}`"
+)]
 pub trait SubCommandGroup: Sized {
     /// Create the command option.
     fn create_option(
@@ -365,6 +1424,22 @@ pub trait SubCommandGroup: Sized {
     ///
     /// Returns an error if the implementation fails.
     fn from_value(value: &CommandDataOptionValue) -> Result<Self>;
+
+    /// Extract data from a [`CommandDataOptionValue`], looking up referenced
+    /// entities (e.g. attachments) in `resolved`.
+    ///
+    /// By default, this delegates to [`SubCommandGroup::from_value`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the implementation fails.
+    fn from_value_resolved(
+        value: &CommandDataOptionValue,
+        resolved: &CommandDataResolved,
+    ) -> Result<Self> {
+        let _ = resolved;
+        Self::from_value(value)
+    }
 }
 
 /// A sub-command which can be nested inside of a [`Command`] or
@@ -372,6 +1447,13 @@ pub trait SubCommandGroup: Sized {
 ///
 /// This is a sub-trait of [`SubCommandGroup`], as a [`SubCommand`] can be used
 /// anywhere a [`SubCommandGroup`] can.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a sub-command",
+    label = "`{Self}` does not implement `SubCommand`",
+    note = "add `#[derive(SubCommand)]` to `{Self}`",
+    note = "if `{Self}` is a sub-command group, it is nested too deeply: Discord only allows \
+            sub-command groups directly inside of top-level commands"
+)]
 pub trait SubCommand: SubCommandGroup {
     /// Create the command option.
     fn create_option(
@@ -389,12 +1471,101 @@ pub trait SubCommand: SubCommandGroup {
     fn from_value(value: &CommandDataOptionValue) -> Result<Self> {
         <Self as SubCommandGroup>::from_value(value)
     }
+
+    /// Extract data from a [`CommandDataOption`], looking up referenced
+    /// entities (e.g. attachments) in `resolved`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the implementation fails.
+    fn from_value_resolved(
+        value: &CommandDataOptionValue,
+        resolved: &CommandDataResolved,
+    ) -> Result<Self> {
+        <Self as SubCommandGroup>::from_value_resolved(value, resolved)
+    }
+}
+
+/// A set of options which can be flattened into a command or sub-command with
+/// `#[command(flatten)]`.
+///
+/// As flattening goes through this trait rather than the fields of the
+/// flattened type, types from other crates can be flattened as long as they
+/// implement it.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be flattened into a command",
+    label = "`{Self}` does not implement `FlattenOptions`",
+    note = "add `#[derive(FlattenOptions)]` or `#[derive(SubCommand)]` to `{Self}`"
+)]
+pub trait FlattenOptions: Sized {
+    /// Create the options.
+    fn create_options() -> Vec<CreateCommandOption>;
+
+    /// Extract data from the options of a command or sub-command.
+    ///
+    /// Options which do not belong to this set of options are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the implementation fails.
+    fn from_options(options: &[CommandDataOption]) -> Result<Self>;
+
+    /// Extract data from the options of a command or sub-command, looking up
+    /// referenced entities (e.g. attachments) in `resolved`.
+    ///
+    /// By default, this delegates to [`FlattenOptions::from_options`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the implementation fails.
+    fn from_options_resolved(
+        options: &[CommandDataOption],
+        resolved: &CommandDataResolved,
+    ) -> Result<Self> {
+        let _ = resolved;
+        Self::from_options(options)
+    }
+}
+
+/// Find the value of the option named `name` within a sub-command's options,
+/// without parsing the whole sub-command.
+///
+/// This is useful for cheaply inspecting a single option (e.g. in middleware)
+/// before fully parsing the sub-command.
+///
+/// # Errors
+///
+/// Returns an error if `value` is not a sub-command, or if it has no option
+/// named `name`.
+pub fn subcommand_option<'a>(
+    value: &'a CommandDataOptionValue,
+    name: &str,
+) -> Result<&'a CommandDataOptionValue> {
+    let CommandDataOptionValue::SubCommand(options) = value else {
+        return Err(Error::IncorrectCommandOptionType {
+            name: None,
+            got: value.kind(),
+            expected: CommandOptionType::SubCommand,
+        });
+    };
+
+    options
+        .iter()
+        .find(|option| option.name == name)
+        .map(|option| &option.value)
+        .ok_or(Error::MissingRequiredCommandOption)
 }
 
 /// A basic option which can be nested inside of [`Command`]s or
 /// [`SubCommand`]s.
 ///
 /// This trait is implemented already for most primitive types.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as a command option",
+    label = "`{Self}` does not implement `BasicOption`",
+    note = "add `#[derive(BasicOption)]` to `{Self}`, or implement `BasicOption` manually",
+    note = "command options must be owned, so use e.g. `String` instead of `&str`"
+)]
 pub trait BasicOption: Sized {
     /// Create the command option.
     fn create_option(
@@ -408,6 +1579,93 @@ pub trait BasicOption: Sized {
     ///
     /// Returns an error if the implementation fails.
     fn from_value(value: Option<&CommandDataOptionValue>) -> Result<Self>;
+
+    /// Extract data from an owned [`CommandDataOptionValue`].
+    ///
+    /// By default, this delegates to [`BasicOption::from_value`].
+    /// Implementations can override this to move data out of `value`
+    /// instead of cloning it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the implementation fails.
+    fn into_value(value: Option<CommandDataOptionValue>) -> Result<Self> {
+        Self::from_value(value.as_ref())
+    }
+
+    /// Extract data from a [`CommandDataOptionValue`] belonging to the command
+    /// option named `name`.
+    ///
+    /// The derives call this rather than [`BasicOption::from_value`]. By
+    /// default, this delegates to [`BasicOption::from_value`] and attaches
+    /// `name` to the error with [`Error::with_option_name`]. Implementations
+    /// can override this to describe their errors in more detail, e.g. with
+    /// [`Error::custom_for`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the implementation fails.
+    fn from_value_named(name: &str, value: Option<&CommandDataOptionValue>) -> Result<Self> {
+        Self::from_value(value).map_err(|error| error.with_option_name(name))
+    }
+
+    /// Extract data from a [`CommandDataOptionValue`] belonging to the command
+    /// option named `name`, looking up referenced entities in `resolved`.
+    ///
+    /// The derives call this rather than [`BasicOption::from_value_named`],
+    /// passing along [`CommandData::resolved`]. By default, this delegates to
+    /// [`BasicOption::from_value_named`], only using `resolved` to turn an
+    /// [`Error::IncorrectCommandOptionType`] for a mentionable into an
+    /// [`Error::IncorrectMentionableKind`].
+    /// Implementations which need more than the ID carried by the option
+    /// itself, like [`ResolvedAttachment`], override this.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the implementation fails.
+    fn from_value_resolved(
+        name: &str,
+        value: Option<&CommandDataOptionValue>,
+        resolved: &CommandDataResolved,
+    ) -> Result<Self> {
+        Self::from_value_named(name, value)
+            .map_err(|error| error.with_mentionable_kind(value, resolved))
+    }
+}
+
+/// A [`BasicOption`] which restricts its values to a fixed set of choices.
+///
+/// This is implemented by [`BasicOption`]'s derive macro, and allows other
+/// options to reuse the same choices with `#[command(choices_from = "...")]`.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_commands::{BasicOption, Command};
+///
+/// #[derive(BasicOption)]
+/// #[choice(option_type = "integer")]
+/// enum Medal {
+///     #[choice(value = 1)]
+///     Gold,
+///
+///     #[choice(value = 2)]
+///     Silver,
+///
+///     #[choice(value = 3)]
+///     Bronze,
+/// }
+///
+/// #[derive(Command)]
+/// struct Rank {
+///     /// The rank to show.
+///     #[command(choices_from = "Medal")]
+///     rank: i64,
+/// }
+/// ```
+pub trait ChoiceOption: BasicOption {
+    /// Add this option's choices to a command option.
+    fn add_choices(option: CreateCommandOption) -> CreateCommandOption;
 }
 
 macro_rules! impl_command_option {
@@ -424,7 +1682,27 @@ macro_rules! impl_command_option {
 
                     match value {
                         CommandDataOptionValue::$Variant(v) => Ok(v.clone() as _),
+                        CommandDataOptionValue::Autocomplete { .. } => {
+                            Err(Error::UnexpectedAutocompleteOption)
+                        }
+                        _ => Err(Error::IncorrectCommandOptionType {
+                            name: None,
+                            got: value.kind(),
+                            expected: CommandOptionType::$Variant,
+                        }),
+                    }
+                }
+
+                fn into_value(value: Option<CommandDataOptionValue>) -> Result<Self> {
+                    let value = value.ok_or(Error::MissingRequiredCommandOption)?;
+
+                    match value {
+                        CommandDataOptionValue::$Variant(v) => Ok(v),
+                        CommandDataOptionValue::Autocomplete { .. } => {
+                            Err(Error::UnexpectedAutocompleteOption)
+                        }
                         _ => Err(Error::IncorrectCommandOptionType {
+                            name: None,
                             got: value.kind(),
                             expected: CommandOptionType::$Variant,
                         }),
@@ -460,7 +1738,11 @@ macro_rules! impl_number_command_option {
                     #[allow(clippy::cast_possible_truncation)]
                     match value {
                         CommandDataOptionValue::Number(v) => Ok(*v as _),
+                        CommandDataOptionValue::Autocomplete { .. } => {
+                            Err(Error::UnexpectedAutocompleteOption)
+                        }
                         _ => Err(Error::IncorrectCommandOptionType {
+                            name: None,
                             got: value.kind(),
                             expected: CommandOptionType::Number,
                         }),
@@ -474,32 +1756,70 @@ macro_rules! impl_number_command_option {
 
 impl_number_command_option!(f32, f64);
 
+/// The largest integer Discord accepts for integer options.
+const MAX_SAFE_INTEGER: u64 = 1 << 53;
+
 macro_rules! impl_integer_command_option {
     ($($Ty:ty),* $(,)?) => {
         $(
             impl BasicOption for $Ty {
+                /// Sets [`CreateCommandOption::min_int_value`] and
+                /// [`CreateCommandOption::max_int_value`] to this type's range,
+                /// where it is narrower than the range Discord accepts.
+                ///
+                /// Negative minimums cannot be expressed with
+                /// [`CreateCommandOption::min_int_value`], so signed types only
+                /// set a maximum.
+                #[allow(irrefutable_let_patterns, clippy::unnecessary_fallible_conversions)]
                 fn create_option(name: impl Into<String>, description: impl Into<String>) -> CreateCommandOption {
-                    CreateCommandOption::new(CommandOptionType::Integer, name, description)
-                        .required(true)
-                }
+                    let mut option = CreateCommandOption::new(CommandOptionType::Integer, name, description)
+                        .required(true);
 
+                    if let Ok(min) = u64::try_from(<$Ty>::MIN) {
+                        option = option.min_int_value(min);
+                    }
+
+                    if let Ok(max) = u64::try_from(<$Ty>::MAX) {
+                        if max < MAX_SAFE_INTEGER {
+                            option = option.max_int_value(max);
+                        }
+                    }
 
+                    option
+                }
+
+                /// Fails with [`Error::Custom`] if the value does not fit in
+                /// this type, rather than truncating it.
                 fn from_value(value: Option<&CommandDataOptionValue>) -> Result<Self> {
                     let value = value.ok_or(Error::MissingRequiredCommandOption)?;
 
-                    #[allow(
-                        clippy::cast_possible_truncation,
-                        clippy::cast_sign_loss,
-                        clippy::cast_lossless
-                    )]
+                    #[allow(clippy::unnecessary_fallible_conversions)]
                     match value {
-                        CommandDataOptionValue::Integer(v) => Ok(*v as _),
+                        CommandDataOptionValue::Integer(v) => Self::try_from(*v).map_err(Error::custom),
+                        CommandDataOptionValue::Autocomplete { .. } => {
+                            Err(Error::UnexpectedAutocompleteOption)
+                        }
                         _ => Err(Error::IncorrectCommandOptionType {
+                            name: None,
                             got: value.kind(),
                             expected: CommandOptionType::Integer,
                         }),
                     }
                 }
+
+                /// Fails with [`Error::InvalidValue`] if the value does not fit
+                /// in this type, rather than truncating it.
+                fn from_value_named(
+                    name: &str,
+                    value: Option<&CommandDataOptionValue>,
+                ) -> Result<Self> {
+                    #[allow(clippy::unnecessary_fallible_conversions)]
+                    match value {
+                        Some(CommandDataOptionValue::Integer(v)) => Self::try_from(*v)
+                            .map_err(|error| Error::custom_for(name, v.to_string(), error)),
+                        _ => Self::from_value(value).map_err(|error| error.with_option_name(name)),
+                    }
+                }
             }
         )*
     };
@@ -507,6 +1827,28 @@ macro_rules! impl_integer_command_option {
 
 impl_integer_command_option!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
 
+impl BasicOption for Cow<'_, str> {
+    /// Delegates to [`String`]'s [`BasicOption::create_option`] implementation.
+    fn create_option(
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> CreateCommandOption {
+        String::create_option(name, description)
+    }
+
+    /// Delegates to [`String`]'s [`BasicOption::from_value`] implementation,
+    /// always producing [`Cow::Owned`].
+    fn from_value(value: Option<&CommandDataOptionValue>) -> Result<Self> {
+        String::from_value(value).map(Cow::Owned)
+    }
+
+    /// Delegates to [`String`]'s [`BasicOption::into_value`] implementation,
+    /// always producing [`Cow::Owned`].
+    fn into_value(value: Option<CommandDataOptionValue>) -> Result<Self> {
+        String::into_value(value).map(Cow::Owned)
+    }
+}
+
 impl<T: BasicOption> BasicOption for Option<T> {
     /// Delegates to `T`'s [`BasicOption::create_option`] implementation, but
     /// sets [`CreateCommandOption::required`] to `false` afterwards.
@@ -522,4 +1864,410 @@ impl<T: BasicOption> BasicOption for Option<T> {
     fn from_value(value: Option<&CommandDataOptionValue>) -> Result<Self> {
         value.map(|option| T::from_value(Some(option))).transpose()
     }
+
+    /// Only delegates to `T`'s [`BasicOption::into_value`] implementation if
+    /// `value` is [`Some`].
+    fn into_value(value: Option<CommandDataOptionValue>) -> Result<Self> {
+        value.map(|option| T::into_value(Some(option))).transpose()
+    }
+
+    /// Only delegates to `T`'s [`BasicOption::from_value_named`]
+    /// implementation if `value` is [`Some`].
+    fn from_value_named(name: &str, value: Option<&CommandDataOptionValue>) -> Result<Self> {
+        value
+            .map(|option| T::from_value_named(name, Some(option)))
+            .transpose()
+    }
+
+    /// Only delegates to `T`'s [`BasicOption::from_value_resolved`]
+    /// implementation if `value` is [`Some`].
+    fn from_value_resolved(
+        name: &str,
+        value: Option<&CommandDataOptionValue>,
+        resolved: &CommandDataResolved,
+    ) -> Result<Self> {
+        value
+            .map(|option| T::from_value_resolved(name, Some(option), resolved))
+            .transpose()
+    }
+}
+
+macro_rules! impl_wrapper_command_option {
+    ($($Wrapper:ident),* $(,)?) => {
+        $(
+            impl<T: BasicOption> BasicOption for $Wrapper<T> {
+                /// Delegates to `T`'s [`BasicOption::create_option`] implementation.
+                fn create_option(
+                    name: impl Into<String>,
+                    description: impl Into<String>,
+                ) -> CreateCommandOption {
+                    T::create_option(name, description)
+                }
+
+                #[doc = concat!(
+                    "Delegates to `T`'s [`BasicOption::from_value`] implementation, ",
+                    "wrapping the result in a [`", stringify!($Wrapper), "`]."
+                )]
+                fn from_value(value: Option<&CommandDataOptionValue>) -> Result<Self> {
+                    T::from_value(value).map($Wrapper::new)
+                }
+
+                #[doc = concat!(
+                    "Delegates to `T`'s [`BasicOption::into_value`] implementation, ",
+                    "wrapping the result in a [`", stringify!($Wrapper), "`]."
+                )]
+                fn into_value(value: Option<CommandDataOptionValue>) -> Result<Self> {
+                    T::into_value(value).map($Wrapper::new)
+                }
+
+                #[doc = concat!(
+                    "Delegates to `T`'s [`BasicOption::from_value_named`] implementation, ",
+                    "wrapping the result in a [`", stringify!($Wrapper), "`]."
+                )]
+                fn from_value_named(
+                    name: &str,
+                    value: Option<&CommandDataOptionValue>,
+                ) -> Result<Self> {
+                    T::from_value_named(name, value).map($Wrapper::new)
+                }
+
+                #[doc = concat!(
+                    "Delegates to `T`'s [`BasicOption::from_value_resolved`] implementation, ",
+                    "wrapping the result in a [`", stringify!($Wrapper), "`]."
+                )]
+                fn from_value_resolved(
+                    name: &str,
+                    value: Option<&CommandDataOptionValue>,
+                    resolved: &CommandDataResolved,
+                ) -> Result<Self> {
+                    T::from_value_resolved(name, value, resolved).map($Wrapper::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_wrapper_command_option!(Box, Arc);
+
+/// A [`BasicOption`] which is not required, and falls back to `T`'s [`Default`]
+/// implementation when it is not provided.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_commands::{Command, Defaulted};
+///
+/// #[derive(Command)]
+/// struct List {
+///     /// The page to show.
+///     page: Defaulted<u32>,
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Defaulted<T>(pub T);
+
+impl<T> Defaulted<T> {
+    /// Unwrap the inner value.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Defaulted<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Defaulted<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: BasicOption + Default> BasicOption for Defaulted<T> {
+    /// Delegates to `T`'s [`BasicOption::create_option`] implementation, but
+    /// sets [`CreateCommandOption::required`] to `false` afterwards.
+    fn create_option(
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> CreateCommandOption {
+        T::create_option(name, description).required(false)
+    }
+
+    /// Delegates to `T`'s [`BasicOption::from_value`] implementation if `value`
+    /// is [`Some`], otherwise uses `T`'s [`Default`] implementation.
+    fn from_value(value: Option<&CommandDataOptionValue>) -> Result<Self> {
+        value
+            .map_or_else(|| Ok(T::default()), |option| T::from_value(Some(option)))
+            .map(Self)
+    }
+
+    /// Delegates to `T`'s [`BasicOption::into_value`] implementation if `value`
+    /// is [`Some`], otherwise uses `T`'s [`Default`] implementation.
+    fn into_value(value: Option<CommandDataOptionValue>) -> Result<Self> {
+        value
+            .map_or_else(|| Ok(T::default()), |option| T::into_value(Some(option)))
+            .map(Self)
+    }
+
+    /// Delegates to `T`'s [`BasicOption::from_value_named`] implementation if
+    /// `value` is [`Some`], otherwise uses `T`'s [`Default`] implementation.
+    fn from_value_named(name: &str, value: Option<&CommandDataOptionValue>) -> Result<Self> {
+        value
+            .map_or_else(
+                || Ok(T::default()),
+                |option| T::from_value_named(name, Some(option)),
+            )
+            .map(Self)
+    }
+
+    /// Delegates to `T`'s [`BasicOption::from_value_resolved`] implementation
+    /// if `value` is [`Some`], otherwise uses `T`'s [`Default`] implementation.
+    fn from_value_resolved(
+        name: &str,
+        value: Option<&CommandDataOptionValue>,
+        resolved: &CommandDataResolved,
+    ) -> Result<Self> {
+        value
+            .map_or_else(
+                || Ok(T::default()),
+                |option| T::from_value_resolved(name, Some(option), resolved),
+            )
+            .map(Self)
+    }
+}
+
+/// A [`String`] [`BasicOption`] whose length (in characters) must be within
+/// `MIN..=MAX`.
+///
+/// The bounds are sent to Discord with
+/// [`CreateCommandOption::min_length`] and [`CreateCommandOption::max_length`],
+/// and are checked again when parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity_commands::{BoundedString, Command};
+///
+/// #[derive(Command)]
+/// struct Airport {
+///     /// The three-letter airport code.
+///     code: BoundedString<3, 3>,
+/// }
+/// ```
+///
+/// Bounds which no string could satisfy, with `MIN` greater than `MAX`, fail
+/// to build (though not to `cargo check`, as the bounds are only checked once
+/// the option is used):
+///
+/// ```rust,compile_fail
+/// use serenity_commands::{BoundedString, Command};
+///
+/// #[derive(Command)]
+/// struct Airport {
+///     /// The three-letter airport code.
+///     code: BoundedString<4, 3>,
+/// }
+///
+/// Airport::create_command("airport", "Look up an airport.");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedString<const MIN: u16, const MAX: u16>(pub String);
+
+impl<const MIN: u16, const MAX: u16> BoundedString<MIN, MAX> {
+    /// Fails to compile if `MIN` is greater than `MAX`, as no string could
+    /// satisfy the bounds.
+    const VALID_BOUNDS: () = assert!(MIN <= MAX, "`BoundedString` requires `MIN <= MAX`");
+
+    /// Unwrap the inner value.
+    #[must_use]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    fn new(string: String) -> Result<Self> {
+        let () = Self::VALID_BOUNDS;
+
+        let length = string.chars().count();
+
+        if (usize::from(MIN)..=usize::from(MAX)).contains(&length) {
+            Ok(Self(string))
+        } else {
+            Err(Error::custom(format!(
+                "expected between {MIN} and {MAX} characters, got {length}"
+            )))
+        }
+    }
+}
+
+impl<const MIN: u16, const MAX: u16> Deref for BoundedString<MIN, MAX> {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const MIN: u16, const MAX: u16> BasicOption for BoundedString<MIN, MAX> {
+    /// Delegates to [`String`]'s [`BasicOption::create_option`] implementation,
+    /// setting [`CreateCommandOption::min_length`] and
+    /// [`CreateCommandOption::max_length`] afterwards.
+    fn create_option(
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> CreateCommandOption {
+        let () = Self::VALID_BOUNDS;
+
+        String::create_option(name, description)
+            .min_length(MIN)
+            .max_length(MAX)
+    }
+
+    /// Delegates to [`String`]'s [`BasicOption::from_value`] implementation,
+    /// then checks the length of the string.
+    fn from_value(value: Option<&CommandDataOptionValue>) -> Result<Self> {
+        String::from_value(value).and_then(Self::new)
+    }
+
+    /// Delegates to [`String`]'s [`BasicOption::into_value`] implementation,
+    /// then checks the length of the string.
+    fn into_value(value: Option<CommandDataOptionValue>) -> Result<Self> {
+        String::into_value(value).and_then(Self::new)
+    }
+}
+
+macro_rules! impl_resolved_command_option {
+    ($(
+        $(#[$attr:meta])*
+        $Resolved:ident($Target:ty) from $Id:ty => |$id:ident, $resolved:ident| $lookup:expr;
+    )*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone)]
+            pub struct $Resolved(pub $Target);
+
+            impl $Resolved {
+                /// Unwrap the inner value.
+                #[must_use]
+                pub fn into_inner(self) -> $Target {
+                    self.0
+                }
+            }
+
+            impl Deref for $Resolved {
+                type Target = $Target;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            impl BasicOption for $Resolved {
+                #[doc = concat!(
+                    "Delegates to [`", stringify!($Id), "`]'s ",
+                    "[`BasicOption::create_option`] implementation."
+                )]
+                fn create_option(
+                    name: impl Into<String>,
+                    description: impl Into<String>,
+                ) -> CreateCommandOption {
+                    <$Id>::create_option(name, description)
+                }
+
+                /// Always fails with [`Error::MissingResolvedValue`], as the
+                /// value can only be found in the resolved data.
+                fn from_value(value: Option<&CommandDataOptionValue>) -> Result<Self> {
+                    <$Id>::from_value(value)?;
+
+                    Err(Error::MissingResolvedValue)
+                }
+
+                #[doc = concat!(
+                    "Delegates to [`", stringify!($Id), "`]'s ",
+                    "[`BasicOption::from_value_named`] implementation, then looks up ",
+                    "the value in `resolved`."
+                )]
+                fn from_value_resolved(
+                    name: &str,
+                    value: Option<&CommandDataOptionValue>,
+                    $resolved: &CommandDataResolved,
+                ) -> Result<Self> {
+                    let $id = <$Id>::from_value_named(name, value)?;
+
+                    $lookup.map(Self).ok_or(Error::MissingResolvedValue)
+                }
+            }
+        )*
+    };
+}
+
+impl_resolved_command_option! {
+    /// An attachment [`BasicOption`] which resolves to the full [`Attachment`],
+    /// rather than just its [`AttachmentId`].
+    ///
+    /// Attachment options only carry the ID of the uploaded file, and the
+    /// [`Attachment`] itself is sent alongside in [`CommandData::resolved`].
+    /// This is looked up by [`BasicOption::from_value_resolved`], which the
+    /// derives call with the resolved data of the interaction. As
+    /// [`BasicOption::from_value`] has no access to the resolved data, it
+    /// always fails with [`Error::MissingResolvedValue`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_commands::{Command, ResolvedAttachment};
+    ///
+    /// #[derive(Command)]
+    /// struct Upload {
+    ///     /// The file to upload.
+    ///     file: ResolvedAttachment,
+    /// }
+    /// ```
+    ResolvedAttachment(Attachment) from AttachmentId => |id, resolved| {
+        resolved.attachments.get(&id).cloned()
+    };
+
+    /// A user [`BasicOption`] which resolves to the full [`User`], rather than
+    /// just their [`UserId`].
+    ///
+    /// Like [`ResolvedAttachment`], the [`User`] is looked up in
+    /// [`CommandData::resolved`].
+    ResolvedUser(User) from UserId => |id, resolved| resolved.users.get(&id).cloned();
+
+    /// A user [`BasicOption`] which resolves to the guild member, for access
+    /// to e.g. their nickname and roles.
+    ///
+    /// Like [`ResolvedAttachment`], the member is looked up in
+    /// [`CommandData::resolved`]. Discord only sends a [`PartialMember`]
+    /// there, so its [`PartialMember::user`] is filled in from the resolved
+    /// users. Parsing fails with [`Error::MissingResolvedValue`] if the user is
+    /// not a member of the guild, or the command was not used in a guild.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity_commands::{Command, ResolvedMember, ResolvedUser};
+    ///
+    /// #[derive(Command)]
+    /// struct Whois {
+    ///     /// The member to look up.
+    ///     member: ResolvedMember,
+    ///
+    ///     /// Who is asking.
+    ///     asker: Option<ResolvedUser>,
+    /// }
+    /// ```
+    ResolvedMember(PartialMember) from UserId => |id, resolved| {
+        resolved.members.get(&id).cloned().map(|mut member| {
+            if member.user.is_none() {
+                member.user = resolved.users.get(&id).cloned();
+            }
+
+            member
+        })
+    };
 }
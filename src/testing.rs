@@ -0,0 +1,105 @@
+//! Fixtures for testing command parsing without a connection to Discord.
+//!
+//! [`CommandData`] cannot be constructed directly, so these build it by
+//! deserializing the JSON Discord would send.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use serenity::all::CommandOptionType;
+//! use serenity_commands::{
+//!     testing::{command_data, option},
+//!     Commands,
+//! };
+//!
+//! #[derive(Debug, PartialEq, Commands)]
+//! enum AllCommands {
+//!     /// Echo a message.
+//!     Echo {
+//!         /// The message to echo.
+//!         message: String,
+//!     },
+//! }
+//!
+//! let data = command_data(
+//!     "echo",
+//!     [option("message", CommandOptionType::String, "hello")],
+//! );
+//!
+//! assert_eq!(
+//!     AllCommands::from_command_data(&data).unwrap(),
+//!     AllCommands::Echo {
+//!         message: "hello".to_owned(),
+//!     }
+//! );
+//! ```
+
+use serde_json::{json, Value};
+use serenity::all::{CommandData, CommandOptionType};
+
+/// Build the [`CommandData`] Discord would send for the command `name` with
+/// the given raw `options`.
+///
+/// # Panics
+///
+/// Panics if `options` are not valid command data options.
+#[must_use]
+pub fn command_data(name: &str, options: impl IntoIterator<Item = Value>) -> CommandData {
+    command_data_resolved(name, options, json!({}))
+}
+
+/// Build the [`CommandData`] Discord would send for the command `name` with
+/// the given raw `options` and raw `resolved` data, for testing options like
+/// [`ResolvedAttachment`](crate::ResolvedAttachment).
+///
+/// # Panics
+///
+/// Panics if `options` or `resolved` are not valid command data.
+#[must_use]
+pub fn command_data_resolved(
+    name: &str,
+    options: impl IntoIterator<Item = Value>,
+    resolved: Value,
+) -> CommandData {
+    let mut data = json!({
+        "id": "1",
+        "name": name,
+        "type": 1,
+        "options": options.into_iter().collect::<Vec<_>>(),
+    });
+    data["resolved"] = resolved;
+
+    serde_json::from_value(data).expect("command data should be valid")
+}
+
+/// Build a raw command option named `name` of type `kind` with the given
+/// `value`.
+#[must_use]
+pub fn option(name: &str, kind: CommandOptionType, value: impl Into<Value>) -> Value {
+    json!({
+        "name": name,
+        "type": u8::from(kind),
+        "value": value.into(),
+    })
+}
+
+/// Build a raw sub-command named `name` with the given raw `options`.
+#[must_use]
+pub fn sub_command(name: &str, options: impl IntoIterator<Item = Value>) -> Value {
+    json!({
+        "name": name,
+        "type": u8::from(CommandOptionType::SubCommand),
+        "options": options.into_iter().collect::<Vec<_>>(),
+    })
+}
+
+/// Build a raw sub-command group named `name` with the given raw
+/// `sub_commands`.
+#[must_use]
+pub fn sub_command_group(name: &str, sub_commands: impl IntoIterator<Item = Value>) -> Value {
+    json!({
+        "name": name,
+        "type": u8::from(CommandOptionType::SubCommandGroup),
+        "options": sub_commands.into_iter().collect::<Vec<_>>(),
+    })
+}
@@ -0,0 +1,74 @@
+#![allow(missing_docs, dead_code)]
+
+use serenity_commands::{
+    testing::{self, command_data},
+    Command, Commands, Error, SubCommand,
+};
+
+#[derive(Debug, SubCommand)]
+struct BinaryOperation {
+    /// The first number.
+    a: f64,
+
+    /// The second number.
+    b: f64,
+}
+
+#[test]
+fn first_error() {
+    #[derive(Debug, Commands)]
+    enum AllCommands {
+        /// Perform math operations.
+        Math(MathCommand),
+    }
+
+    #[derive(Debug, Command)]
+    enum MathCommand {
+        /// Add two numbers.
+        Add(BinaryOperation),
+    }
+
+    let data = command_data("math", [testing::sub_command("add", [])]);
+
+    assert!(matches!(
+        AllCommands::from_command_data_verbose(&data)
+            .as_ref()
+            .map_err(Vec::as_slice),
+        Err([Error::MissingRequiredCommandOption])
+    ));
+}
+
+#[test]
+fn collect_errors() {
+    #[derive(Debug, Commands)]
+    #[command(collect_errors)]
+    enum AllCommands {
+        /// Divide two numbers.
+        Divide {
+            /// The dividend.
+            a: f64,
+
+            /// The divisor.
+            b: f64,
+        },
+
+        /// Compare two numbers.
+        Compare(CompareCommand),
+    }
+
+    #[derive(Debug, Command)]
+    #[command(collect_errors)]
+    struct CompareCommand {
+        /// The first number.
+        a: f64,
+
+        /// The second number.
+        b: f64,
+    }
+
+    for name in ["divide", "compare"] {
+        let errors = AllCommands::from_command_data_verbose(&command_data(name, [])).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+    }
+}
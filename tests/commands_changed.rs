@@ -0,0 +1,154 @@
+#![allow(missing_docs, dead_code)]
+
+use serde_json::{json, Value};
+use serenity::all::{Command, CommandData, CreateCommand, Permissions};
+use serenity_commands::{Commands, Error, Result};
+
+#[derive(Commands)]
+enum AllCommands {
+    /// Ping the bot.
+    Ping,
+
+    /// Echo a message.
+    Echo {
+        /// The message to echo.
+        message: String,
+
+        /// The number of times to echo the message.
+        times: Option<i64>,
+    },
+}
+
+/// A command which can only be used by members who can ban others.
+struct BanCommand;
+
+impl Commands for BanCommand {
+    fn create_commands() -> Vec<CreateCommand> {
+        vec![CreateCommand::new("ban")
+            .description("Ban a member.")
+            .default_member_permissions(Permissions::BAN_MEMBERS)]
+    }
+
+    fn from_command_data(data: &CommandData) -> Result<Self> {
+        match data.name.as_str() {
+            "ban" => Ok(Self),
+            name => Err(Error::UnknownCommand(name.to_owned())),
+        }
+    }
+}
+
+/// Deserialize a command as Discord reports it, filling in the fields which
+/// Discord sets on its own.
+fn existing(mut command: Value) -> Command {
+    let fields = json!({
+        "id": "1",
+        "application_id": "2",
+        "type": 1,
+        "version": "3",
+    });
+
+    for (key, value) in fields.as_object().unwrap() {
+        command[key] = value.clone();
+    }
+
+    serde_json::from_value(command).expect("command should be valid")
+}
+
+fn ping() -> Command {
+    existing(json!({
+        "name": "ping",
+        "description": "Ping the bot.",
+    }))
+}
+
+fn echo(message_description: &str) -> Command {
+    existing(json!({
+        "name": "echo",
+        "description": "Echo a message.",
+        "options": [
+            {
+                "type": 3,
+                "name": "message",
+                "description": message_description,
+                "required": true,
+            },
+            {
+                "type": 4,
+                "name": "times",
+                "description": "The number of times to echo the message.",
+            },
+        ],
+    }))
+}
+
+fn ban(default_member_permissions: Option<&str>) -> Command {
+    existing(json!({
+        "name": "ban",
+        "description": "Ban a member.",
+        "default_member_permissions": default_member_permissions,
+    }))
+}
+
+#[test]
+fn unchanged() {
+    assert!(!AllCommands::commands_changed(&[
+        ping(),
+        echo("The message to echo.")
+    ]));
+}
+
+#[test]
+fn unchanged_in_any_order() {
+    assert!(!AllCommands::commands_changed(&[
+        echo("The message to echo."),
+        ping()
+    ]));
+}
+
+#[test]
+fn changed_option() {
+    assert!(AllCommands::commands_changed(&[
+        ping(),
+        echo("The message to repeat.")
+    ]));
+}
+
+#[test]
+fn missing_command() {
+    assert!(AllCommands::commands_changed(&[ping()]));
+}
+
+#[test]
+fn extra_command() {
+    assert!(AllCommands::commands_changed(&[
+        ping(),
+        echo("The message to echo."),
+        ban(None),
+    ]));
+}
+
+#[test]
+fn unchanged_default_member_permissions() {
+    let bits = Permissions::BAN_MEMBERS.bits().to_string();
+
+    assert!(!BanCommand::commands_changed(&[ban(Some(&bits))]));
+}
+
+#[test]
+fn changed_default_member_permissions() {
+    let bits = Permissions::KICK_MEMBERS.bits().to_string();
+
+    assert!(BanCommand::commands_changed(&[ban(Some(&bits))]));
+    assert!(BanCommand::commands_changed(&[ban(None)]));
+}
+
+#[test]
+fn added_default_member_permissions() {
+    let mut ping = ping();
+    ping.default_member_permissions = Some(Permissions::ADMINISTRATOR);
+
+    assert!(AllCommands::commands_changed(&[
+        ping,
+        echo("The message to echo.")
+    ]));
+}
@@ -0,0 +1,231 @@
+#![allow(missing_docs)]
+
+use serde_json::Value;
+use serenity::all::{CommandData, CommandOptionType};
+use serenity_commands::{
+    testing::{self, command_data, option},
+    Command, Commands, Error, SubCommand,
+};
+
+#[derive(Debug, Commands)]
+enum AllCommands {
+    /// Ping the bot.
+    Ping,
+
+    /// Echo a message.
+    Echo {
+        /// The message to echo.
+        message: String,
+    },
+
+    /// Perform math operations.
+    Math(MathCommand),
+}
+
+impl AllCommands {
+    fn run(self) -> String {
+        match self {
+            Self::Ping => "Pong!".to_string(),
+            Self::Echo { message } => message,
+            Self::Math(math) => math.run().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Command)]
+enum MathCommand {
+    /// Add two numbers.
+    Add(BinaryOperation),
+
+    /// Negate a number.
+    Negate {
+        /// The number to negate.
+        a: f64,
+    },
+}
+
+impl MathCommand {
+    fn run(self) -> f64 {
+        match self {
+            Self::Add(BinaryOperation { a, b }) => a + b,
+            Self::Negate { a } => -a,
+        }
+    }
+}
+
+#[derive(Debug, SubCommand)]
+struct BinaryOperation {
+    /// The first number.
+    a: f64,
+
+    /// The second number.
+    b: f64,
+}
+
+fn run(name: &str, options: impl IntoIterator<Item = Value>) -> serenity_commands::Result<String> {
+    AllCommands::from_command_data(&command_data(name, options)).map(AllCommands::run)
+}
+
+#[test]
+fn create_commands() {
+    let commands = AllCommands::create_commands();
+
+    assert_eq!(commands.len(), 3);
+    assert_eq!(AllCommands::create_commands_iter().count(), 3);
+}
+
+#[test]
+fn create_commands_filtered() {
+    let names = AllCommands::create_commands_filtered(|name| name != "echo")
+        .into_iter()
+        .map(|command| serde_json::to_value(command).unwrap()["name"].take())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["ping", "math"]);
+}
+
+#[test]
+fn command_names() {
+    /// Show the bot's status.
+    #[derive(Commands)]
+    #[command(name = "bot-status")]
+    struct StatusCommand;
+
+    assert_eq!(AllCommands::COMMAND_NAMES, ["ping", "echo", "math"]);
+    assert_eq!(StatusCommand::COMMAND_NAMES, ["bot-status"]);
+    assert_eq!(StatusCommand::NAME, "bot-status");
+}
+
+#[test]
+fn prefixed_command_names() {
+    #[allow(non_camel_case_types)]
+    #[derive(Debug, Commands)]
+    #[command(prefix = "acme-")]
+    enum AllCommands {
+        /// Ping the bot.
+        PingPong,
+
+        /// Look up a user.
+        #[command(verbatim)]
+        get_user,
+
+        /// Echo a message.
+        #[command(name = "say")]
+        Echo,
+    }
+
+    assert_eq!(
+        AllCommands::COMMAND_NAMES,
+        ["acme-ping-pong", "acme-get_user", "say"]
+    );
+    assert!(matches!(
+        AllCommands::from_command_data(&command_data("acme-get_user", [])),
+        Ok(AllCommands::get_user)
+    ));
+}
+
+#[test]
+fn unit_command() {
+    assert_eq!(run("ping", []).unwrap(), "Pong!");
+}
+
+#[test]
+fn named_command() {
+    let options = [option("message", CommandOptionType::String, "hello")];
+
+    assert_eq!(run("echo", options).unwrap(), "hello");
+}
+
+#[test]
+fn sub_command() {
+    let options = [testing::sub_command(
+        "add",
+        [
+            option("a", CommandOptionType::Number, 1.5),
+            option("b", CommandOptionType::Number, 2),
+        ],
+    )];
+
+    assert_eq!(run("math", options).unwrap(), "3.5");
+}
+
+#[test]
+fn named_sub_command() {
+    let options = [testing::sub_command(
+        "negate",
+        [option("a", CommandOptionType::Number, 4)],
+    )];
+
+    assert_eq!(run("math", options).unwrap(), "-4");
+}
+
+#[test]
+fn unknown_command() {
+    assert!(matches!(
+        run("unknown", []),
+        Err(Error::UnknownCommand(name)) if name == "unknown"
+    ));
+}
+
+#[test]
+fn missing_option() {
+    assert!(matches!(
+        run("echo", []),
+        Err(Error::MissingRequiredCommandOption)
+    ));
+}
+
+#[test]
+fn missing_sub_command() {
+    assert!(matches!(run("math", []), Err(Error::MissingSubCommand)));
+}
+
+#[tokio::test]
+async fn other_command() {
+    #[allow(clippy::large_enum_variant)]
+    #[derive(Debug, Commands)]
+    #[command(dispatch)]
+    enum AllCommands {
+        /// Ping the bot.
+        Ping,
+
+        #[command(other)]
+        Unknown(CommandData),
+    }
+
+    struct Handler;
+
+    impl AllCommandsRun for Handler {
+        type Output = String;
+
+        async fn ping(&self) -> String {
+            "Pong!".to_string()
+        }
+
+        async fn unknown(&self, command: CommandData) -> String {
+            format!("unknown command `{}`", command.name)
+        }
+    }
+
+    assert_eq!(AllCommands::COMMAND_NAMES, ["ping"]);
+    assert_eq!(AllCommands::create_commands().len(), 1);
+
+    // The `other` variant has no name of its own, so even a command named
+    // after it falls through with its data intact.
+    for name in ["forward", "unknown"] {
+        let data = command_data(name, [option("target", CommandOptionType::String, "x")]);
+        let command = AllCommands::from_command_data(&data).unwrap();
+
+        assert!(
+            matches!(&command, AllCommands::Unknown(other) if other.name == name && other.options.len() == 1)
+        );
+        assert_eq!(
+            command.run(&Handler).await,
+            format!("unknown command `{name}`")
+        );
+    }
+
+    let command = AllCommands::from_command_data(&command_data("ping", [])).unwrap();
+
+    assert_eq!(command.run(&Handler).await, "Pong!");
+}
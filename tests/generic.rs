@@ -0,0 +1,46 @@
+#![allow(missing_docs)]
+
+use serenity::all::CommandOptionType;
+use serenity_commands::{
+    testing::{command_data, option},
+    Command, Commands, FlattenOptions,
+};
+
+#[derive(Debug, PartialEq, FlattenOptions)]
+struct Range<T> {
+    /// The lower bound.
+    min: T,
+
+    /// The upper bound.
+    max: T,
+}
+
+#[derive(Debug, PartialEq, Command)]
+struct FilterCommand<T> {
+    #[command(flatten)]
+    range: Range<T>,
+}
+
+#[derive(Debug, PartialEq, Commands)]
+enum AllCommands<T> {
+    /// Filter values by a range.
+    Filter(FilterCommand<T>),
+}
+
+#[test]
+fn generic_flatten() {
+    let data = command_data(
+        "filter",
+        [
+            option("min", CommandOptionType::Integer, 1),
+            option("max", CommandOptionType::Integer, 5),
+        ],
+    );
+
+    assert_eq!(
+        AllCommands::<i64>::from_command_data(&data).unwrap(),
+        AllCommands::Filter(FilterCommand {
+            range: Range { min: 1, max: 5 }
+        })
+    );
+}
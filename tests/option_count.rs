@@ -0,0 +1,111 @@
+#![allow(missing_docs, dead_code)]
+
+use serde_json::Value;
+use serenity::all::CommandOptionType;
+use serenity_commands::{
+    testing::{command_data, option, sub_command, sub_command_group},
+    Command, Commands, Error, SubCommand, SubCommandGroup,
+};
+
+/// More options than Discord allows in a single list.
+const TOO_MANY: usize = 26;
+
+#[derive(Debug, Commands)]
+enum AllCommands {
+    /// Perform math operations.
+    Math(MathCommand),
+}
+
+#[derive(Debug, Command)]
+enum MathCommand {
+    /// Add two numbers.
+    Add(AddSubCommand),
+
+    /// Trigonometric operations.
+    Trig(TrigGroup),
+}
+
+#[derive(Debug, SubCommandGroup)]
+enum TrigGroup {
+    /// Take the sine of a number.
+    Sin {
+        /// The number to take the sine of.
+        a: f64,
+    },
+}
+
+#[derive(Debug, SubCommand)]
+struct AddSubCommand {
+    /// The first number.
+    a: f64,
+
+    /// The second number.
+    b: f64,
+}
+
+fn parse(options: impl IntoIterator<Item = Value>) -> serenity_commands::Result<AllCommands> {
+    AllCommands::from_command_data(&command_data("math", options))
+}
+
+fn number(name: &str) -> Value {
+    option(name, CommandOptionType::Number, 1)
+}
+
+#[test]
+fn empty_command() {
+    assert!(matches!(parse([]), Err(Error::MissingSubCommand)));
+}
+
+#[test]
+fn over_full_command() {
+    let options = (0..TOO_MANY).map(|_| sub_command("add", [number("a"), number("b")]));
+
+    assert!(matches!(
+        parse(options),
+        Err(Error::IncorrectCommandOptionCount {
+            got: TOO_MANY,
+            expected: 1,
+        })
+    ));
+}
+
+#[test]
+fn empty_sub_command_group() {
+    assert!(matches!(
+        parse([sub_command_group("trig", [])]),
+        Err(Error::MissingSubCommand)
+    ));
+}
+
+#[test]
+fn over_full_sub_command_group() {
+    let sub_commands = (0..TOO_MANY).map(|_| sub_command("sin", [number("a")]));
+
+    assert!(matches!(
+        parse([sub_command_group("trig", sub_commands)]),
+        Err(Error::IncorrectCommandOptionCount {
+            got: TOO_MANY,
+            expected: 1,
+        })
+    ));
+}
+
+#[test]
+fn empty_sub_command() {
+    assert!(matches!(
+        parse([sub_command("add", [])]),
+        Err(Error::MissingRequiredCommandOption)
+    ));
+}
+
+#[test]
+fn over_full_sub_command() {
+    let options = [number("a"), number("b")]
+        .into_iter()
+        .chain((2..TOO_MANY).map(|idx| number(&format!("extra-{idx}"))));
+
+    assert!(matches!(
+        parse([sub_command("add", options)]),
+        Ok(AllCommands::Math(MathCommand::Add(AddSubCommand { .. })))
+    ));
+}
@@ -0,0 +1,66 @@
+#![allow(missing_docs, dead_code)]
+
+use serenity_commands::{BasicOption, Command};
+
+type MaybeNumber = Option<f64>;
+
+#[derive(BasicOption)]
+#[choice(option_type = "string", builder(required(false)))]
+enum Color {
+    Red,
+    Blue,
+}
+
+#[derive(Command)]
+struct Aliased {
+    /// An optional number.
+    a: Option<f64>,
+
+    /// An aliased optional number.
+    b: MaybeNumber,
+}
+
+#[derive(Command)]
+struct Boxed {
+    /// An optional number.
+    a: Option<f64>,
+
+    /// A boxed optional number.
+    b: Box<Option<f64>>,
+}
+
+#[derive(Command)]
+struct Choice {
+    /// An optional number.
+    a: Option<f64>,
+
+    /// An optional color.
+    color: Color,
+}
+
+/// Whether each option of `C` is required.
+fn required<C: Command>() -> Vec<bool> {
+    let command = serde_json::to_value(C::create_command("command", "A command.")).unwrap();
+
+    command["options"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|option| option["required"].as_bool().unwrap())
+        .collect()
+}
+
+#[test]
+fn aliased_optional() {
+    assert_eq!(required::<Aliased>(), [false, false]);
+}
+
+#[test]
+fn boxed_optional() {
+    assert_eq!(required::<Boxed>(), [false, false]);
+}
+
+#[test]
+fn optional_choice() {
+    assert_eq!(required::<Choice>(), [false, false]);
+}
@@ -0,0 +1,22 @@
+use serenity_commands::Command;
+
+#[derive(Command)]
+struct Search {
+    /// The query to search for.
+    #[command(builder(min_length(10), max_length(5)))]
+    query: String,
+
+    /// The tag to search for.
+    #[command(builder(max_length(0)))]
+    tag: Option<String>,
+
+    /// The page to show.
+    #[command(builder(min_int_value(10), max_int_value(-10)))]
+    page: Option<i64>,
+
+    /// The minimum score of results.
+    #[command(builder(min_number_value(1.5), max_number_value(0.5)))]
+    score: Option<f64>,
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+use serenity_commands::BasicOption;
+
+#[derive(BasicOption)]
+#[choice(option_type = "integer", value_from_name)]
+enum Medal {
+    #[choice(value = "gold")]
+    Gold,
+
+    Silver,
+
+    Bronze = 3,
+}
+
+#[derive(BasicOption)]
+#[choice(option_type = "number")]
+enum Constant {
+    #[choice(value = "pi")]
+    Pi,
+
+    E,
+}
+
+#[derive(BasicOption)]
+#[choice(option_type = "string")]
+enum Color {
+    #[choice(value = 1)]
+    Red,
+}
+
+fn main() {}
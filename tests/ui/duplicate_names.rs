@@ -0,0 +1,38 @@
+use serenity_commands::{Command, FlattenOptions};
+
+#[derive(FlattenOptions)]
+struct Pagination {
+    /// The page to show.
+    page: Option<i64>,
+}
+
+#[derive(Command)]
+struct Search {
+    /// The query to search for.
+    query: String,
+
+    /// The query to search for, again.
+    #[command(name = "query")]
+    other_query: String,
+
+    #[command(flatten)]
+    pagination: Pagination,
+}
+
+#[derive(Command)]
+enum Lookup {
+    /// Look up a URL.
+    GetUrl {
+        /// The URL.
+        url: String,
+    },
+
+    /// Look up a URL, again.
+    #[command(name = "get-url")]
+    GetLink {
+        /// The URL.
+        url: String,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use serenity_commands::Command;
+
+#[derive(Command)]
+struct Add {
+    a: f64,
+
+    b: f64,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use serenity_commands::SubCommandGroup;
+
+#[derive(SubCommandGroup)]
+enum Math {
+    /// Negate a number.
+    Negate {
+        /// The number to negate.
+        a: f64,
+    },
+}
+
+#[derive(SubCommandGroup)]
+enum TooDeep {
+    /// Math operations.
+    Math(Math),
+}
+
+fn main() {}
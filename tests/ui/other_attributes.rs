@@ -0,0 +1,13 @@
+use serenity::all::CommandData;
+use serenity_commands::Commands;
+
+#[derive(Commands)]
+enum AllCommands {
+    /// Ping the bot.
+    Ping,
+
+    #[command(other, name = "fallback", builder(nsfw(true)), nsfw)]
+    Unknown(CommandData),
+}
+
+fn main() {}
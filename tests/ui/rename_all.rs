@@ -0,0 +1,24 @@
+use serenity_commands::BasicOption;
+
+#[derive(BasicOption)]
+#[choice(option_type = "string", value_from_name, rename_all = "snake_case")]
+enum Theme {
+    DarkMode,
+    LightMode,
+}
+
+#[derive(BasicOption)]
+#[choice(option_type = "integer", rename_all = "snake_case")]
+enum Medal {
+    Gold = 1,
+    Silver = 2,
+}
+
+#[derive(BasicOption)]
+#[choice(option_type = "number", rename_all = "snake_case")]
+enum Constant {
+    #[choice(value = 3.14)]
+    Pi,
+}
+
+fn main() {}
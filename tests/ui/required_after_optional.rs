@@ -0,0 +1,45 @@
+use serenity_commands::{BasicOption, Command};
+
+type MaybeNumber = Option<f64>;
+
+#[derive(BasicOption)]
+#[choice(option_type = "string", builder(required(false)))]
+enum Color {
+    Red,
+    Blue,
+}
+
+#[derive(Command)]
+struct Aliased {
+    /// An aliased optional number.
+    a: MaybeNumber,
+
+    /// An optional number.
+    b: Option<f64>,
+
+    /// A required number.
+    c: f64,
+}
+
+#[derive(Command)]
+struct Boxed {
+    /// A boxed optional number.
+    a: Box<Option<f64>>,
+
+    /// A required number.
+    b: f64,
+}
+
+#[derive(Command)]
+struct Choice {
+    /// An optional color.
+    color: Color,
+
+    /// An optional number.
+    a: Option<f64>,
+
+    /// A required number.
+    b: f64,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use serenity_commands::SubCommandGroup;
+
+struct Negate {
+    a: f64,
+}
+
+#[derive(SubCommandGroup)]
+enum Math {
+    /// Negate a number.
+    Negate(Negate),
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use serenity_commands::Commands;
+
+#[derive(Commands)]
+#[allow(non_camel_case_types)]
+enum AllCommands {
+    /// Look up a user.
+    #[command(verbatim)]
+    lookupUser {
+        /// The user's ID.
+        #[command(verbatim)]
+        user_id: u64,
+    },
+}
+
+#[derive(Commands)]
+#[command(prefix = "acme-")]
+#[allow(non_camel_case_types)]
+enum PrefixedCommands {
+    /// Look up a user.
+    #[command(verbatim)]
+    lookupUser,
+}
+
+fn main() {}